@@ -17,4 +17,61 @@ pub struct ResultResponse {
     pub data: Vec<DataEntry>,
     pub raw_data: String,
     pub generated_jupyter: Option<String>,
+    pub run_id: Option<String>, // Run of the Benchmark that produced the result, links the result to the logs of the run.
+}
+
+/// Logs of a workload container, collected by the operator before the workloads of a Benchmark run are removed.
+#[derive(Debug, Serialize)]
+pub struct WorkloadLog {
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: String,
+    pub logs: String,
+}
+
+/// Resource usage of a workload Pod (or one of its containers), sampled by the operator while a Benchmark run is Running.
+/// Every field is optional, because the kubelet only reports the statistics it has available.
+#[derive(Debug, Serialize)]
+pub struct ResourceUsageSample {
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: Option<String>, // `None` for the totals of the Pod, which include its network usage.
+    pub cpu_usage_nano_cores: Option<i64>,
+    pub memory_working_set_bytes: Option<i64>,
+    pub network_rx_bytes: Option<i64>, // Cumulative, only reported for the Pod.
+    pub network_tx_bytes: Option<i64>, // Cumulative, only reported for the Pod.
+    pub disk_used_bytes: Option<i64>, // Root filesystem of the container, or the ephemeral storage of the Pod.
+}
+
+/// Environment a workload Pod of a Benchmark run ran in, captured by the operator when the run started.
+/// Results of runs can be filtered and compared on the node, the software versions and the hardware they ran on.
+#[derive(Debug, Serialize)]
+pub struct WorkloadEnvironment {
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub node: String,
+    pub kubernetes_version: String, // Version of the API server.
+    pub kubelet_version: String,
+    pub container_runtime_version: String, // E.g., `containerd://1.7.1`.
+    pub kernel_version: String,
+    pub os_image: String,
+    pub architecture: String,
+    pub cpu_model: Option<String>, // Only known when the node is labeled by Node Feature Discovery.
+    pub allocatable_cpu: Option<String>,
+    pub allocatable_memory: Option<String>,
+    pub node_labels: String,   // JSON object with the labels of the node.
+    pub image_digests: String, // JSON object with the image (including its digest) of every container of the Pod.
+}
+
+/// Membership of a Benchmark run in the run of a BenchmarkSuite, recorded by the operator when the Benchmark starts.
+/// The results of the runs of a suite run are shown together as one assessment.
+#[derive(Debug, Serialize)]
+pub struct SuiteRun {
+    pub suite_run_id: String,
+    pub suite_name: String,
+    pub namespace: String,
+    pub benchmark_name: String,
 }
@@ -2,6 +2,7 @@ from typing import List
 from sqlalchemy import ForeignKey
 from sqlalchemy import Text
 from sqlalchemy import ARRAY
+from sqlalchemy import BigInteger
 from sqlalchemy import Double
 from sqlalchemy import DateTime
 from sqlalchemy.sql import func
@@ -28,9 +29,10 @@ class BenchmarkResults(Base):
     timestamp: Mapped[DateTime] = mapped_column(
         DateTime(timezone=True), server_default=func.now())
     generated_jupyter: Mapped[str] = mapped_column(Text(), nullable=True)
+    run_id: Mapped[str] = mapped_column(Text(), nullable=True)
 
     def __repr__(self) -> str:
-        return f"BenchmarkResults(id={self.id}, name={self.name}, description={self.description}, data={self.data}, raw_data={self.raw_data}, timestamp={self.timestamp}, generated_jupyter={self.generated_jupyter})"
+        return f"BenchmarkResults(id={self.id}, name={self.name}, description={self.description}, data={self.data}, raw_data={self.raw_data}, timestamp={self.timestamp}, generated_jupyter={self.generated_jupyter}, run_id={self.run_id})"
 
 
 class BenchmarkData(Base):
@@ -47,3 +49,89 @@ class BenchmarkData(Base):
 
     def __repr__(self) -> str:
         return f"BenchmarkData(id={self.id}, parameter={self.parameter}, data_unit={self.data_unit}, measurements={self.measurements})"
+
+
+class BenchmarkLogs(Base):
+    __tablename__ = "benchmark_logs"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+    run_id: Mapped[str] = mapped_column(Text(), index=True)
+    benchmark_name: Mapped[str] = mapped_column(Text())
+    namespace: Mapped[str] = mapped_column(Text())
+    pod: Mapped[str] = mapped_column(Text())
+    container: Mapped[str] = mapped_column(Text())
+    logs: Mapped[str] = mapped_column(Text())
+    timestamp: Mapped[DateTime] = mapped_column(
+        DateTime(timezone=True), server_default=func.now())
+
+    def __repr__(self) -> str:
+        return f"BenchmarkLogs(id={self.id}, run_id={self.run_id}, benchmark_name={self.benchmark_name}, pod={self.pod}, container={self.container})"
+
+
+class BenchmarkResourceUsage(Base):
+    __tablename__ = "benchmark_resource_usage"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+    run_id: Mapped[str] = mapped_column(Text(), index=True)
+    benchmark_name: Mapped[str] = mapped_column(Text())
+    namespace: Mapped[str] = mapped_column(Text())
+    pod: Mapped[str] = mapped_column(Text())
+    container: Mapped[str] = mapped_column(Text(), nullable=True)
+    cpu_usage_nano_cores: Mapped[int] = mapped_column(BigInteger(), nullable=True)
+    memory_working_set_bytes: Mapped[int] = mapped_column(
+        BigInteger(), nullable=True)
+    network_rx_bytes: Mapped[int] = mapped_column(BigInteger(), nullable=True)
+    network_tx_bytes: Mapped[int] = mapped_column(BigInteger(), nullable=True)
+    disk_used_bytes: Mapped[int] = mapped_column(BigInteger(), nullable=True)
+    timestamp: Mapped[DateTime] = mapped_column(
+        DateTime(timezone=True), server_default=func.now())
+
+    def __repr__(self) -> str:
+        return f"BenchmarkResourceUsage(id={self.id}, run_id={self.run_id}, pod={self.pod}, container={self.container}, timestamp={self.timestamp})"
+
+
+class BenchmarkEnvironment(Base):
+    __tablename__ = "benchmark_environments"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+    run_id: Mapped[str] = mapped_column(Text(), index=True)
+    benchmark_name: Mapped[str] = mapped_column(Text())
+    namespace: Mapped[str] = mapped_column(Text())
+    pod: Mapped[str] = mapped_column(Text())
+    node: Mapped[str] = mapped_column(Text())
+    kubernetes_version: Mapped[str] = mapped_column(Text())
+    kubelet_version: Mapped[str] = mapped_column(Text())
+    container_runtime_version: Mapped[str] = mapped_column(Text())
+    kernel_version: Mapped[str] = mapped_column(Text())
+    os_image: Mapped[str] = mapped_column(Text())
+    architecture: Mapped[str] = mapped_column(Text())
+    cpu_model: Mapped[str] = mapped_column(Text(), nullable=True)
+    allocatable_cpu: Mapped[str] = mapped_column(Text(), nullable=True)
+    allocatable_memory: Mapped[str] = mapped_column(Text(), nullable=True)
+    node_labels: Mapped[str] = mapped_column(Text())
+    image_digests: Mapped[str] = mapped_column(Text())
+    timestamp: Mapped[DateTime] = mapped_column(
+        DateTime(timezone=True), server_default=func.now())
+
+    def __repr__(self) -> str:
+        return f"BenchmarkEnvironment(id={self.id}, run_id={self.run_id}, pod={self.pod}, node={self.node}, kernel_version={self.kernel_version})"
+
+
+class BenchmarkSuiteRuns(Base):
+    __tablename__ = "benchmark_suite_runs"
+
+    id: Mapped[int] = mapped_column(primary_key=True)
+
+    suite_run_id: Mapped[str] = mapped_column(Text(), index=True)
+    suite_name: Mapped[str] = mapped_column(Text())
+    namespace: Mapped[str] = mapped_column(Text())
+    benchmark_name: Mapped[str] = mapped_column(Text())
+    run_id: Mapped[str] = mapped_column(Text(), index=True)
+    timestamp: Mapped[DateTime] = mapped_column(
+        DateTime(timezone=True), server_default=func.now())
+
+    def __repr__(self) -> str:
+        return f"BenchmarkSuiteRuns(id={self.id}, suite_run_id={self.suite_run_id}, suite_name={self.suite_name}, benchmark_name={self.benchmark_name}, run_id={self.run_id})"
@@ -3,6 +3,7 @@ from sqlalchemy_utils import database_exists, create_database
 from sqlalchemy import select
 from sqlalchemy import update
 from sqlalchemy import null
+from sqlalchemy import text
 from sqlalchemy.orm import Session
 from orm import Base
 from orm import BenchmarkResults
@@ -28,6 +29,11 @@ def main():
 
     Base.metadata.create_all(engine)
 
+    # `create_all` does not add columns to existing tables.
+    with engine.begin() as connection:
+        connection.execute(text(
+            "ALTER TABLE benchmark_results ADD COLUMN IF NOT EXISTS run_id TEXT"))
+
     print("Analysis runner started.", flush=True)
 
     while True:
@@ -18,9 +18,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let database_url = grpc_call.benchmark_started().await?;
 
     // Run the benchmark and capture the results.
-    let result_response = benchmark_linux_kernel();
+    let mut result_response = benchmark_linux_kernel();
 
-    // Store the results in the database.
+    // Store the results in the database, linked to the run of the Benchmark.
+    result_response.run_id = grpc_call.run_id.clone();
     DatabaseInterface::save_results(database_url, result_response).await?;
 
     // Call `benchmark_done` to mark this benchmark as done.
@@ -73,6 +74,7 @@ fn benchmark_linux_kernel() -> ResultResponse {
         description: "This benchmark will compile the Linux kernel a couple of times, testing CPU performance.".into(),
         data: vec![data_entry],
         raw_data: result,
-        generated_jupyter: None
+        generated_jupyter: None,
+        run_id: None
     }
 }
@@ -36,6 +36,7 @@ pub fn adapt_critertion_results(
         data: Vec::new(),
         raw_data: raw_json_data.into(),
         generated_jupyter: None,
+        run_id: None,
     };
 
     // Loop over every JSON object (one per line) in the raw results.
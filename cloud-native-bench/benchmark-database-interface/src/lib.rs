@@ -1,6 +1,8 @@
 use std::error::Error;
 
-use benchmark_adapter_types::ResultResponse;
+use benchmark_adapter_types::{
+    ResourceUsageSample, ResultResponse, SuiteRun, WorkloadEnvironment, WorkloadLog,
+};
 use sqlx::{Connection, PgConnection, Row};
 
 pub struct DatabaseInterface {}
@@ -13,11 +15,12 @@ impl DatabaseInterface {
         let mut conn = PgConnection::connect(&database_url).await?;
 
         let insert_result = sqlx::query(
-            "INSERT INTO benchmark_results (name, description, raw_data) VALUES ($1, $2, $3) RETURNING id;",
+            "INSERT INTO benchmark_results (name, description, raw_data, run_id) VALUES ($1, $2, $3, $4) RETURNING id;",
         )
         .bind(result_response.name)
         .bind(result_response.description)
         .bind(result_response.raw_data)
+        .bind(result_response.run_id)
         .fetch_one(&mut conn)
         .await?;
 
@@ -38,4 +41,115 @@ impl DatabaseInterface {
 
         Ok(())
     }
+
+    /// Saves the logs of the workload containers of a Benchmark run.
+    pub async fn save_logs(
+        database_url: String,
+        run_id: String,
+        workload_logs: Vec<WorkloadLog>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut conn = PgConnection::connect(&database_url).await?;
+
+        for workload_log in workload_logs {
+            sqlx::query(
+                "INSERT INTO benchmark_logs(run_id, benchmark_name, namespace, pod, container, logs) VALUES ($1, $2, $3, $4, $5, $6);"
+            )
+            .bind(&run_id)
+            .bind(workload_log.benchmark_name)
+            .bind(workload_log.namespace)
+            .bind(workload_log.pod)
+            .bind(workload_log.container)
+            .bind(workload_log.logs)
+            .execute(&mut conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves resource usage samples of the workloads of a Benchmark run, the samples are timestamped by the database.
+    pub async fn save_resource_usage(
+        database_url: String,
+        run_id: String,
+        resource_usage_samples: Vec<ResourceUsageSample>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut conn = PgConnection::connect(&database_url).await?;
+
+        for resource_usage_sample in resource_usage_samples {
+            sqlx::query(
+                "INSERT INTO benchmark_resource_usage(run_id, benchmark_name, namespace, pod, container, cpu_usage_nano_cores, memory_working_set_bytes, network_rx_bytes, network_tx_bytes, disk_used_bytes) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10);"
+            )
+            .bind(&run_id)
+            .bind(resource_usage_sample.benchmark_name)
+            .bind(resource_usage_sample.namespace)
+            .bind(resource_usage_sample.pod)
+            .bind(resource_usage_sample.container)
+            .bind(resource_usage_sample.cpu_usage_nano_cores)
+            .bind(resource_usage_sample.memory_working_set_bytes)
+            .bind(resource_usage_sample.network_rx_bytes)
+            .bind(resource_usage_sample.network_tx_bytes)
+            .bind(resource_usage_sample.disk_used_bytes)
+            .execute(&mut conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves the environment of the workload Pods of a Benchmark run.
+    pub async fn save_environment(
+        database_url: String,
+        run_id: String,
+        workload_environments: Vec<WorkloadEnvironment>,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut conn = PgConnection::connect(&database_url).await?;
+
+        for workload_environment in workload_environments {
+            sqlx::query(
+                "INSERT INTO benchmark_environments(run_id, benchmark_name, namespace, pod, node, kubernetes_version, kubelet_version, container_runtime_version, kernel_version, os_image, architecture, cpu_model, allocatable_cpu, allocatable_memory, node_labels, image_digests) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16);"
+            )
+            .bind(&run_id)
+            .bind(workload_environment.benchmark_name)
+            .bind(workload_environment.namespace)
+            .bind(workload_environment.pod)
+            .bind(workload_environment.node)
+            .bind(workload_environment.kubernetes_version)
+            .bind(workload_environment.kubelet_version)
+            .bind(workload_environment.container_runtime_version)
+            .bind(workload_environment.kernel_version)
+            .bind(workload_environment.os_image)
+            .bind(workload_environment.architecture)
+            .bind(workload_environment.cpu_model)
+            .bind(workload_environment.allocatable_cpu)
+            .bind(workload_environment.allocatable_memory)
+            .bind(workload_environment.node_labels)
+            .bind(workload_environment.image_digests)
+            .execute(&mut conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Links a Benchmark run to the run of its suite.
+    pub async fn save_suite_run(
+        database_url: String,
+        run_id: String,
+        suite_run: SuiteRun,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut conn = PgConnection::connect(&database_url).await?;
+
+        sqlx::query(
+            "INSERT INTO benchmark_suite_runs(suite_run_id, suite_name, namespace, benchmark_name, run_id) VALUES ($1, $2, $3, $4, $5);"
+        )
+        .bind(suite_run.suite_run_id)
+        .bind(suite_run.suite_name)
+        .bind(suite_run.namespace)
+        .bind(suite_run.benchmark_name)
+        .bind(run_id)
+        .execute(&mut conn)
+        .await?;
+
+        Ok(())
+    }
 }
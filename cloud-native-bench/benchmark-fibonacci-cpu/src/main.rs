@@ -18,9 +18,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Run the benchmark and capture the results.
     let results = benchmark_fibonacci();
 
-    let result_response = benchmark_criterion_result_adapter::adapt_critertion_results(results.as_str(), "Fibonacci benchmark", "This benchmark will run the fibonacci calculation for [5, 6, 7, 8, 9, 10], 100 samples each, each sample consisting of a lot (worst case only one) of iterations.", "Time");
+    let mut result_response = benchmark_criterion_result_adapter::adapt_critertion_results(results.as_str(), "Fibonacci benchmark", "This benchmark will run the fibonacci calculation for [5, 6, 7, 8, 9, 10], 100 samples each, each sample consisting of a lot (worst case only one) of iterations.", "Time");
 
-    // Store the results in the database.
+    // Store the results in the database, linked to the run of the Benchmark.
+    result_response.run_id = grpc_call.run_id.clone();
     DatabaseInterface::save_results(database_url, result_response).await?;
 
     // Call `benchmark_done` to mark this benchmark as done.
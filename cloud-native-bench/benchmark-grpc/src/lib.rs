@@ -6,6 +6,7 @@ use tonic::{transport::Channel, Status};
 
 pub struct GrpcCall {
     pub grpc_client: BenchmarkServiceClient<Channel>,
+    pub run_id: Option<String>, // Run of the Benchmark, known once `benchmark_started` is called.
 }
 
 impl GrpcCall {
@@ -13,16 +14,24 @@ impl GrpcCall {
     pub async fn connect(endpoint: String) -> Result<Self, Box<dyn Error>> {
         Ok(Self {
             grpc_client: BenchmarkServiceClient::connect(endpoint).await?,
+            run_id: None,
         })
     }
 
     /// Lets the operator know the Benchmark has started.
     /// Returns the database connection URL string that the Benchmark should use to save its results.
+    /// The run of the Benchmark is stored in `run_id`.
     pub async fn benchmark_started(&mut self) -> Result<String, Status> {
         let request = tonic::Request::new(BenchmarkStartRequest { running: true });
-        let response = self.grpc_client.benchmark_started(request).await?;
+        let response = self
+            .grpc_client
+            .benchmark_started(request)
+            .await?
+            .into_inner();
 
-        Ok(response.into_inner().database_connection_string)
+        self.run_id = Some(response.run_id).filter(|run_id| !run_id.is_empty());
+
+        Ok(response.database_connection_string)
     }
 
     /// Lets the operator know the Benchmark has finished and all the results are saved to the database.
@@ -1,29 +1,45 @@
-use crate::state::{Context, State};
+use crate::{
+    crd_versions::{benchmark_crd, migrate_storage_version},
+    helm,
+    matrix::{matrix_schema, MATRIX_PARENT_LABEL},
+    state::{Context, State},
+};
+use chrono::Utc;
 use futures::StreamExt;
 use k8s_openapi::{
-    api::core::v1::{Pod, PodTemplateSpec},
+    api::{
+        apps::v1::{DeploymentSpec, StatefulSetSpec},
+        batch::v1::JobSpec,
+        core::v1::{
+            ConfigMapKeySelector, ObjectReference, Pod, PodTemplateSpec, ResourceQuotaSpec,
+            ServiceSpec,
+        },
+        networking::v1::NetworkPolicySpec,
+    },
     apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition,
+    apimachinery::pkg::apis::meta::v1::{Condition, Time},
 };
 use kube::{
-    api::{DeleteParams, ListParams, Patch, PatchParams, PostParams},
-    core::ObjectMeta,
+    api::{ListParams, Patch, PatchParams, PostParams},
     error::ErrorResponse,
     runtime::{
         controller::Action,
+        events::EventType,
         finalizer,
+        reflector::ObjectRef,
         wait::{await_condition, conditions},
         watcher, Controller,
     },
-    Api, Client, CustomResource, CustomResourceExt, Error, Resource, ResourceExt,
+    Api, Client, CustomResource, CustomResourceExt, Error, ResourceExt,
+};
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SingleOrVec},
+    JsonSchema,
 };
-use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::{
-    process::{exit, Command, Stdio},
-    sync::Arc,
-    time::Duration,
-};
+use std::{collections::BTreeMap, process::exit, sync::Arc, time::Duration};
 use tracing::{error, info, warn};
 
 /// Metadata that indicates what type of benchmark this is.
@@ -42,43 +58,343 @@ pub enum BenchmarkState {
     Running,   // Benchmark pods started & the benchmark is running.
     Done, // Benchmark pods are done with benchmarking and transmitted the results to the controller.
     Completed, // Controller saved the results and marks this benchmark completed.
+    Failed, // Benchmark did not finish (e.g., it exceeded its timeout), the workloads are removed.
+    Cancelled, // Benchmark was cancelled before it finished, the workloads are removed.
+}
+
+impl BenchmarkState {
+    /// Whether the Benchmark reached a final state and left the queue.
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            BenchmarkState::Completed | BenchmarkState::Failed | BenchmarkState::Cancelled
+        )
+    }
 }
 
 /// The spec for running a Helm chart.
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HelmSpec {
-    pub repository_url: String,  // URL to the helm repository.
+    #[schemars(regex(pattern = r"^https?://.+$"))]
+    pub repository_url: String, // URL to the helm repository.
     pub chart_reference: String, // Reference to the helm chart in the repository.
+    pub version: Option<String>, // Version of the helm chart, defaults to the latest version.
+    pub release_name: Option<String>, // Fixed release name, defaults to `<benchmark name>-<workload index>`.
+    #[serde(default)]
+    #[schemars(schema_with = "preserve_unknown_fields")]
+    pub values: Option<serde_json::Value>, // Inline values, these take precedence over `values_from`.
+    pub values_from: Option<ConfigMapKeySelector>, // Key of a ConfigMap (in the Benchmark namespace) that holds a values file.
+    #[serde(default)]
+    pub wait: bool, // Only consider the workload ready once the Pods of the release are ready.
 }
 
-/// A workload containing a Pod or a Helm chart that needs to be deployed.
+/// Schema of a free-form object (e.g., Helm values), which is not validated by Kubernetes.
+fn preserve_unknown_fields(_: &mut SchemaGenerator) -> Schema {
+    serde_json::from_value(json!({
+        "type": "object",
+        "x-kubernetes-preserve-unknown-fields": true
+    }))
+    .unwrap()
+}
+
+/// Maximum number of workloads of a Benchmark, which bounds the cost of the CRD validation rules.
+const MAX_WORKLOADS: u32 = 100;
+
+/// Schema of the workloads, a Benchmark needs at least one workload and every workload needs exactly one workload kind.
+pub(crate) fn workloads_schema(gen: &mut SchemaGenerator) -> Schema {
+    let mut schema = gen.subschema_for::<Vec<BenchmarkWorkload>>().into_object();
+
+    let array = schema.array();
+    array.min_items = Some(1);
+    array.max_items = Some(MAX_WORKLOADS);
+
+    if let Some(SingleOrVec::Single(items)) = &mut array.items {
+        if let Schema::Object(items) = items.as_mut() {
+            items.extensions.insert(
+                "x-kubernetes-validations".into(),
+                json!([{
+                    "rule": "[has(self.podTemplate), has(self.helmChart), has(self.deployment), has(self.statefulSet), has(self.job), has(self.service), has(self.manifest)].filter(x, x).size() == 1",
+                    "message": "Exactly one of podTemplate, helmChart, deployment, statefulSet, job, service or manifest must be set."
+                }]),
+            );
+        }
+    }
+
+    Schema::Object(schema)
+}
+
+/// A workload that needs to be deployed, exactly one of the workload kinds should be set.
+/// Every created object is labelled with the UID of the Benchmark and owned by the Benchmark.
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct BenchmarkWorkload {
+    pub name: Option<String>, // Name of the created object, defaults to `<benchmark name>-<workload index>` (Pods get a generated name).
+    // The unset workload kinds are left out, so the `has()` checks of the CRD validation rule only see the set workload kind.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub pod_template: Option<PodTemplateSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub helm_chart: Option<HelmSpec>,
+    // The schemas of the controller specs are not embedded, they would make the CRD exceed the size limit of the API server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "preserve_unknown_fields")]
+    pub deployment: Option<DeploymentSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "preserve_unknown_fields")]
+    pub stateful_set: Option<StatefulSetSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "preserve_unknown_fields")]
+    pub job: Option<JobSpec>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "preserve_unknown_fields")]
+    pub manifest: Option<serde_json::Value>, // Arbitrary inline manifest, cluster-scoped objects are not owned by the Benchmark.
+    #[serde(default)]
+    pub depends_on: Vec<String>, // Names of the workloads that need to be ready before this workload is created.
+    pub readiness: Option<WorkloadReadiness>, // Without readiness condition, the workload is ready once it is created.
+}
+
+impl BenchmarkWorkload {
+    /// Number of workload kinds that are set, a valid workload has exactly one.
+    pub fn kind_count(&self) -> usize {
+        [
+            self.pod_template.is_some(),
+            self.helm_chart.is_some(),
+            self.deployment.is_some(),
+            self.stateful_set.is_some(),
+            self.job.is_some(),
+            self.service.is_some(),
+            self.manifest.is_some(),
+        ]
+        .into_iter()
+        .filter(|is_set| *is_set)
+        .count()
+    }
+
+    /// Whether this workload runs Pods that are labelled with the UID of the Benchmark.
+    pub fn has_pods(&self) -> bool {
+        self.pod_template.is_some()
+            || self.deployment.is_some()
+            || self.stateful_set.is_some()
+            || self.job.is_some()
+    }
+}
+
+/// Settings of the namespace the workloads of an isolated Benchmark run in.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceIsolation {
+    #[serde(default)]
+    pub network_policies: Vec<NetworkPolicySpec>, // NetworkPolicies that are created in the namespace before the workloads.
+    pub resource_quota: Option<ResourceQuotaSpec>, // ResourceQuota that is created in the namespace before the workloads.
+}
+
+/// Condition a workload needs to meet before the workloads that depend on it are created, every set check needs to pass.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadReadiness {
+    #[serde(default)]
+    pub pods_ready: bool, // All Pods of the workload are Ready, the Pods of a Helm chart are selected by their `app.kubernetes.io/instance` label.
+    pub http_get: Option<HttpReadiness>,
+    pub tcp_socket: Option<TcpReadiness>,
+}
+
+/// HTTP readiness check, the workload is ready when a GET request to the URL succeeds.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpReadiness {
+    #[schemars(regex(pattern = r"^https?://.+$"))]
+    pub url: String, // E.g., `http://my-server.default.svc:8080/health`.
+    #[schemars(range(min = 100, max = 599))]
+    pub status_code: Option<u16>, // Expected status code, defaults to any 2xx or 3xx status code.
+}
+
+/// TCP readiness check, the workload is ready when a connection to the port can be opened.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TcpReadiness {
+    pub host: String,
+    #[schemars(range(min = 1))]
+    pub port: u16,
+}
+
+/// Status of a created workload.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadStatus {
+    pub name: String, // Name of the workload, see `Benchmark::workload_name`.
+    pub ready: bool,  // Whether the readiness condition of the workload was met.
+}
+
+/// Failure of a workload Pod (or Job) that failed the Benchmark.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadFailure {
+    pub kind: String, // `Pod` or `Job`.
+    pub name: String,
+    pub container: Option<String>, // Container that failed, if the failure is caused by a container.
+    pub reason: String, // Termination or waiting reason (e.g., `OOMKilled`, `ImagePullBackOff`, `Evicted`, etc.).
+    pub message: Option<String>,
+    pub exit_code: Option<i32>,
+}
+
+/// A run of a matrix Benchmark, i.e., a child Benchmark with one combination of the matrix parameters.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MatrixRun {
+    pub name: String,
+    pub parameters: BTreeMap<String, String>,
+    pub state: Option<BenchmarkState>, // State of the child Benchmark, `None` until the child is reconciled.
 }
 
 /// Kubernetes CR status object.
 #[derive(Deserialize, Serialize, Clone, Default, Debug, JsonSchema)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct BenchmarkStatus {
     pub state: BenchmarkState,
-    pub queue_position: u32,
+    pub queue_position: u32, // Mirrors the position of the Benchmark in the cluster-wide queue, see `BenchmarkQueue`.
+    pub started_at: Option<Time>, // Time at which the workloads of this Benchmark were started.
+    pub finished_at: Option<Time>, // Time at which the Benchmark reached a final state.
+    pub conditions: Vec<Condition>, // Kubernetes-style conditions, see `CONDITION_STARTED` and `CONDITION_FINISHED`.
+    pub helm_releases: Vec<String>, // Names of the Helm releases the operator installed for this Benchmark.
+    pub manifests: Vec<ObjectReference>, // Objects the operator created from the inline manifests of this Benchmark.
+    pub workloads: Vec<WorkloadStatus>, // The workloads that are created so far, in the order they were created.
+    pub workload_failure: Option<WorkloadFailure>, // The workload failure that failed the Benchmark.
+    pub run_id: Option<String>, // Identifies the run of the Benchmark in the database (i.e., its results and the logs of its workloads).
+    pub matrix_runs: Vec<MatrixRun>, // The runs a matrix Benchmark is expanded into.
+}
+
+/// Label that is added to every workload of a Benchmark, its value is the UID of the Benchmark.
+pub const BENCHMARK_UID_LABEL: &str = "michiel.van.kenhove.ugent.be/benchmark-uid";
+
+/// Label that is added to every workload of a Benchmark, its value is the index of the workload in the spec.
+pub const BENCHMARK_WORKLOAD_LABEL: &str = "michiel.van.kenhove.ugent.be/benchmark-workload";
+
+/// Interval between the readiness checks of the workloads that other workloads depend on.
+const READINESS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Condition that is `True` once the workloads of the Benchmark are started.
+pub const CONDITION_STARTED: &str = "Started";
+
+/// Condition that is `True` once the Benchmark reached a final state, its reason tells why the run ended.
+pub const CONDITION_FINISHED: &str = "Finished";
+
+/// Maximum length of the name of most Kubernetes objects (a DNS subdomain).
+pub const MAX_NAME_LENGTH: usize = 253;
+
+/// Name of an object created for `name`, `<name><suffix>` limited to `max_length` characters.
+/// The name is truncated so the suffix is kept, and it does not end with a `-` or `.` before the suffix.
+pub fn child_name(name: &str, suffix: &str, max_length: usize) -> String {
+    let prefix: String = name
+        .chars()
+        .take(max_length.saturating_sub(suffix.len()))
+        .collect();
+
+    format!("{}{}", prefix.trim_end_matches(['-', '.']), suffix)
+}
+
+/// Sets a condition in `conditions`, the transition time is only updated when the status of the condition changes.
+pub fn set_condition(
+    conditions: &mut Vec<Condition>,
+    condition_type: &str,
+    status: bool,
+    reason: &str,
+    message: &str,
+) {
+    let status = if status { "True" } else { "False" }.to_string();
+
+    if let Some(condition) = conditions
+        .iter_mut()
+        .find(|condition| condition.type_ == condition_type)
+    {
+        if condition.status != status {
+            condition.last_transition_time = Time(Utc::now());
+        }
+
+        condition.status = status;
+        condition.reason = reason.into();
+        condition.message = message.into();
+    } else {
+        conditions.push(Condition {
+            type_: condition_type.into(),
+            status,
+            reason: reason.into(),
+            message: message.into(),
+            last_transition_time: Time(Utc::now()),
+            observed_generation: None,
+        });
+    }
+}
+
+impl BenchmarkStatus {
+    /// Sets a condition, the transition time is only updated when the status of the condition changes.
+    pub fn set_condition(
+        &mut self,
+        condition_type: &str,
+        status: bool,
+        reason: &str,
+        message: &str,
+    ) {
+        set_condition(
+            &mut self.conditions,
+            condition_type,
+            status,
+            reason,
+            message,
+        );
+    }
+
+    /// Marks the workloads of the run `run_id` as started.
+    pub fn start(&mut self, run_id: String) {
+        self.run_id = Some(run_id);
+        self.started_at = Some(Time(Utc::now()));
+        self.set_condition(
+            CONDITION_STARTED,
+            true,
+            "WorkloadsStarted",
+            "The workloads of the Benchmark are started.",
+        );
+    }
+
+    /// Moves the Benchmark to a final `state`, `reason` and `message` tell why the run ended.
+    pub fn finish(&mut self, state: BenchmarkState, reason: &str, message: &str) {
+        self.state = state;
+        self.finished_at = Some(Time(Utc::now()));
+        self.set_condition(CONDITION_FINISHED, true, reason, message);
+    }
+
+    /// Time between the start of the workloads and the final state, `None` when the workloads were never started.
+    pub fn duration(&self) -> Option<Duration> {
+        let started_at = self.started_at.as_ref()?;
+        let finished_at = self.finished_at.as_ref()?;
+
+        (finished_at.0 - started_at.0).to_std().ok()
+    }
+
+    /// Returns the `Finished` condition, if the Benchmark is finished.
+    pub fn finished_condition(&self) -> Option<&Condition> {
+        self.conditions
+            .iter()
+            .find(|condition| condition.type_ == CONDITION_FINISHED)
+    }
 }
 
-/// Benchmark CRD spec.
+/// Benchmark CRD spec, this is the storage version of the CRD (see `crd_versions` for the other served versions).
 #[derive(CustomResource, Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
 #[kube(
     group = "michiel.van.kenhove.ugent.be",
-    version = "v1",
+    version = "v2",
     kind = "Benchmark",
     status = "BenchmarkStatus",
     shortname = "bench",
     category = "all",
     printcolumn = r#"{"name": "State", "type": "string", "jsonPath": ".status.state"}"#,
     printcolumn = r#"{"name": "Queue Position", "type": "integer", "jsonPath": ".status.queuePosition"}"#,
+    printcolumn = r#"{"name": "Priority", "type": "integer", "jsonPath": ".spec.priority", "priority": 1}"#,
+    printcolumn = r#"{"name": "Reason", "type": "string", "jsonPath": ".status.conditions[?(@.type==\"Finished\")].reason", "priority": 1}"#,
+    printcolumn = r#"{"name": "Started", "type": "date", "jsonPath": ".status.startedAt", "priority": 1}"#,
+    printcolumn = r#"{"name": "Finished", "type": "date", "jsonPath": ".status.finishedAt", "priority": 1}"#,
     printcolumn = r#"{"name": "Age", "type": "date", "jsonPath": ".metadata.creationTimestamp"}"#,
     namespaced
 )]
@@ -86,7 +402,31 @@ pub struct BenchmarkStatus {
 pub struct BenchmarkSpec {
     pub title: String,
     pub benchmark_type: BenchmarkType,
+    #[schemars(schema_with = "workloads_schema")]
     pub workloads: Vec<BenchmarkWorkload>,
+    /// Maximum time the Benchmark may take, counted from the moment its workloads are started.
+    /// When exceeded, the workloads are removed and the Benchmark is marked `Failed`. At most 7 days.
+    #[schemars(range(min = 1, max = 604800))]
+    pub timeout_seconds: Option<u64>,
+    /// Pending Benchmarks with a higher priority are started before Pending Benchmarks with a lower priority.
+    /// Benchmarks with the same priority are started in the order they were created, defaults to 0.
+    pub priority: Option<i32>,
+    /// Cancels the Benchmark when set, its workloads are removed and it is marked `Cancelled`.
+    /// Unlike deleting the Benchmark, the CR (and its status) is kept.
+    #[serde(default)]
+    pub cancel: bool,
+    /// Runs the workloads in a fresh namespace of their own, which is deleted (with everything in it) when the Benchmark finishes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolation: Option<NamespaceIsolation>,
+    /// Parameters to run the Benchmark with, the Benchmark is expanded into one run (a child Benchmark) per combination.
+    /// `{{ matrix.<key> }}` in the title and the workloads is replaced by the value of the parameter in the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "matrix_schema")]
+    pub matrix: Option<BTreeMap<String, Vec<String>>>,
+    /// Deletes the Benchmark once it is finished (i.e., `Completed`, `Failed` or `Cancelled`) for this many seconds.
+    /// The results of the Benchmark in the database are kept. Defaults to the operator-wide default, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds_after_finished: Option<u64>,
 }
 
 /// Custom implementation of the Benchmark CR auto-generated derived type for BenchmarkSpec.
@@ -95,18 +435,59 @@ impl Benchmark {
         &self,
         ctx: Arc<Context>,
         benchmark_api: &Api<Benchmark>,
-        pods_api: &Api<Pod>,
     ) -> Result<Action, Error> {
         let namespace = self.namespace().unwrap_or("default".to_string());
         let name = self.name_any();
 
+        // Finished Benchmarks are deleted once their time to live expired.
+        if let Some(action) = self.delete_expired(ctx.k8s_client.clone()).await? {
+            return Ok(action);
+        }
+
+        // A matrix Benchmark is not queued itself, it is expanded into runs that are queued.
+        if self.is_matrix() {
+            return self.reconcile_matrix(ctx.k8s_client.clone()).await;
+        }
+
         if let Some(status_current) = &self.status {
+            // A Benchmark that is about to complete (i.e., Done) already transmitted its results.
+            if self.spec.cancel
+                && matches!(
+                    status_current.state,
+                    BenchmarkState::Pending | BenchmarkState::Running
+                )
+            {
+                self.cancel(ctx).await?;
+
+                return Ok(Action::await_change());
+            }
+
             match status_current.state {
                 BenchmarkState::Pending => {
+                    // The workloads are already started, creating the workloads whose dependencies became ready
+                    // and waiting until the Benchmark reports it is running.
+                    if status_current.started_at.is_some() {
+                        return self.progress_workloads(ctx).await;
+                    }
+
+                    // A workload without workload kind would be silently skipped, the CRD of older operator versions did not reject it.
+                    if let Err(message) = self.validate_workloads() {
+                        self.fail(ctx, "InvalidWorkloads", &message).await?;
+
+                        return Ok(Action::await_change());
+                    }
+
+                    // Dependencies on unknown workloads or circular dependencies would never be ready.
+                    if let Err(message) = self.validate_dependencies() {
+                        self.fail(ctx, "InvalidDependencies", &message).await?;
+
+                        return Ok(Action::await_change());
+                    }
+
                     // Only start new Benchmark if no other Benchmark is running.
                     if !ctx.state_data.read().await.is_benchmark_running {
-                        // This Benchmark should only be started when it is first in queue.
-                        if status_current.queue_position != 0 {
+                        // This Benchmark should only be started when it is first in the cluster-wide queue.
+                        if !ctx.queue.try_start(&namespace, &name).await? {
                             return Ok(Action::await_change());
                         }
 
@@ -116,156 +497,398 @@ impl Benchmark {
                             .await
                             .new_benchmark(name.clone(), namespace.clone());
 
-                        // Start the workloads of this Benchmark.
-                        for workload in self.spec.workloads.iter() {
-                            if let Some(pod_template) = &workload.pod_template {
-                                // Workload is a regular container image.
-                                let pod = Pod {
-                                    metadata: ObjectMeta {
-                                        namespace: Some(namespace.clone()),
-                                        generate_name: Some(format!("{}-", name.clone())),
-                                        owner_references: Some(vec![self
-                                            .controller_owner_ref(&())
-                                            .unwrap()]),
-                                        ..pod_template.metadata.clone().unwrap_or_default()
-                                    },
-                                    spec: pod_template.spec.clone(),
-                                    ..Default::default()
-                                };
-
-                                pods_api.create(&PostParams::default(), &pod).await?;
-                            } else if let Some(helm_spec) = &workload.helm_chart {
-                                // Workload is a helm chart.
-                                Command::new("helm")
-                                    .args([
-                                        "install",
-                                        "-n",
-                                        &namespace,
-                                        "--repo",
-                                        &helm_spec.repository_url,
-                                        &helm_spec.chart_reference,
-                                        "--generate-name",
-                                    ])
-                                    .spawn()
-                                    .map_err(|_| {
-                                        Error::Api(ErrorResponse {
-                                            status: "Error".into(),
-                                            message: "Helm install failed.".into(),
-                                            reason: "Helm install command failed.".into(),
-                                            code: 500,
-                                        })
-                                    })?;
-                            }
-                        }
+                        // Mark the start of the workloads, so they are only started once and the timeout can be tracked.
+                        Benchmark::update_status(
+                            ctx.k8s_client.clone(),
+                            &name,
+                            &namespace,
+                            |status| status.start(self.uid().unwrap_or_default()),
+                        )
+                        .await?;
+
+                        self.publish_event(
+                            ctx.k8s_client.clone(),
+                            EventType::Normal,
+                            "Started",
+                            "The Benchmark is first in queue, starting its workloads.",
+                            None,
+                        )
+                        .await;
+
+                        // Link the run to the run of its suite, before its results come in.
+                        self.save_suite_run().await;
+
+                        // Start the workloads without dependencies, a workload that fails to start fails the Benchmark.
+                        return self.progress_workloads(ctx).await;
                     }
                 }
                 BenchmarkState::Running => {
                     // Set the state to running.
                     ctx.set_is_benchmark_running(true).await;
+
+                    return self.progress_workloads(ctx).await;
                 }
                 BenchmarkState::Done => {
-                    // Remove the workload Pods of the finished Benchmark (i.e., all the Pods in the namespace).
-                    pods_api
-                        .delete_collection(&DeleteParams::default(), &ListParams::default())
-                        .await?;
+                    // Keep the logs of the workloads, they are lost when the workloads are removed.
+                    self.save_logs(ctx.k8s_client.clone()).await;
 
-                    // Remove Helm installs (i.e., all the Helm installs in the namespace).
-                    Benchmark::uninstall_helm_charts(&namespace)?;
+                    // Remove the workloads of the finished Benchmark.
+                    self.remove_workloads(ctx.k8s_client.clone()).await?;
 
                     // Change the status of this CR to Completed.
-                    Benchmark::set_benchmark_state(
+                    let mut duration = None;
+                    Benchmark::update_status(ctx.k8s_client.clone(), &name, &namespace, |status| {
+                        status.finish(
+                            BenchmarkState::Completed,
+                            "Completed",
+                            "The Benchmark finished and its results are saved.",
+                        );
+                        duration = status.duration();
+                    })
+                    .await?;
+
+                    if let Some(duration) = duration {
+                        ctx.metrics
+                            .observe_benchmark_duration("Completed", duration);
+                    }
+
+                    self.publish_event(
                         ctx.k8s_client.clone(),
-                        &name,
-                        &namespace,
-                        BenchmarkState::Completed,
+                        EventType::Normal,
+                        "Completed",
+                        "The Benchmark finished and its results are saved.",
+                        None,
                     )
-                    .await?;
+                    .await;
 
                     // Set the status of the Benchmark.
                     ctx.set_is_benchmark_running(false).await;
                     ctx.set_is_benchmark_done(true).await;
 
                     // Reorder Pending Benchmarks.
-                    Benchmark::reorder_pending_benchmarks(
-                        benchmark_api,
-                        status_current.queue_position,
-                    )
-                    .await?;
+                    Benchmark::reorder_pending_benchmarks(ctx, &namespace, &name).await?;
                 }
                 BenchmarkState::Completed => {
                     info!("Benchmark {} completed.", name);
                 }
+                BenchmarkState::Failed => {
+                    warn!(
+                        "Benchmark {} failed: {}",
+                        name,
+                        status_current
+                            .finished_condition()
+                            .map(|condition| condition.message.as_str())
+                            .unwrap_or_default()
+                    );
+                }
+                BenchmarkState::Cancelled => {
+                    info!("Benchmark {} cancelled.", name);
+                }
             }
         } else {
             // Benchmark was just created and doesn't have a Status object yet.
 
-            // Set pending status and queue position.
-            let mut current_queue_pos: u32 = 0;
-
-            // Loop over Pending and Running Benchmarks in current namespace to determine queue position.
-            for bench in benchmark_api.list(&ListParams::default()).await?.iter() {
-                if let Some(status) = &bench.status {
-                    if (status.state == BenchmarkState::Pending
-                        || status.state == BenchmarkState::Running)
-                        && status.queue_position + 1 > current_queue_pos
-                    {
-                        current_queue_pos = status.queue_position + 1;
-                    }
-                }
-            }
+            // Add this Benchmark to the cluster-wide queue, which determines its queue position.
+            let queue_position = ctx.queue.enqueue(self).await?;
 
             // Set new status of this Benchmark.
             let status = json!({
-                "status": BenchmarkStatus{queue_position: current_queue_pos, state: BenchmarkState::Pending}
+                "status": BenchmarkStatus{queue_position, state: BenchmarkState::Pending, ..Default::default()}
             });
             benchmark_api
                 .patch_status(&name, &PatchParams::default(), &Patch::Merge(&status))
                 .await?;
+
+            self.publish_event(
+                ctx.k8s_client.clone(),
+                EventType::Normal,
+                "Queued",
+                &format!("The Benchmark is queued at position {queue_position}."),
+                None,
+            )
+            .await;
+
+            // Pending Benchmarks with a lower priority moved back in the queue.
+            ctx.queue.sync_queue_positions().await?;
         }
 
         Ok(Action::await_change())
     }
 
-    /// Cleanup is called when a Benchmark CR get removed.
-    /// Because we make use of the Owner principle, any Pods that are created by a Benchmark will be automatically removed by Kubernetes.
-    /// See <https://kubernetes.io/docs/concepts/overview/working-with-objects/owners-dependents/> for more information.
-    pub async fn cleanup(
+    /// Priority of this Benchmark in the queue.
+    pub fn priority(&self) -> i32 {
+        self.spec.priority.unwrap_or_default()
+    }
+
+    /// Time that is left before the timeout of this Benchmark expires, `None` when the Benchmark has no timeout.
+    /// The time is counted from the moment the workloads are started (i.e., now, when they were not started yet).
+    fn remaining_time(&self) -> Option<Duration> {
+        let timeout = Duration::from_secs(self.spec.timeout_seconds?);
+
+        let elapsed = self
+            .status
+            .as_ref()
+            .and_then(|status| status.started_at.as_ref())
+            .and_then(|started_at| (Utc::now() - started_at.0).to_std().ok())
+            .unwrap_or_default();
+
+        Some(timeout.saturating_sub(elapsed))
+    }
+
+    /// Creates the workloads whose dependencies are ready, while the Benchmark did not exceed its timeout.
+    /// The Benchmark is requeued to poll the readiness of the workloads until all of them are created,
+    /// and when the timeout of this Benchmark expires.
+    async fn progress_workloads(&self, ctx: Arc<Context>) -> Result<Action, Error> {
+        let remaining_time = self.remaining_time();
+
+        if remaining_time == Some(Duration::ZERO) {
+            self.fail(
+                ctx,
+                "DeadlineExceeded",
+                &format!(
+                    "Benchmark exceeded its timeout of {} seconds.",
+                    self.spec.timeout_seconds.unwrap_or_default()
+                ),
+            )
+            .await?;
+
+            return Ok(Action::await_change());
+        }
+
+        // A crashed workload would leave the Benchmark active until its timeout.
+        if let Some(workload_failure) = self.workload_failure(ctx.k8s_client.clone()).await? {
+            let message = format!(
+                "Workload {} {} failed ({}){}",
+                workload_failure.kind,
+                workload_failure.name,
+                workload_failure.reason,
+                workload_failure
+                    .message
+                    .as_ref()
+                    .map(|message| format!(": {message}"))
+                    .unwrap_or_default()
+            );
+
+            Benchmark::update_status(
+                ctx.k8s_client.clone(),
+                &self.name_any(),
+                &self.namespace().unwrap_or("default".to_string()),
+                |status| status.workload_failure = Some(workload_failure.clone()),
+            )
+            .await?;
+
+            self.publish_event(
+                ctx.k8s_client.clone(),
+                EventType::Warning,
+                "WorkloadFailed",
+                &message,
+                Some(ObjectReference {
+                    api_version: Some(
+                        if workload_failure.kind == "Job" {
+                            "batch/v1"
+                        } else {
+                            "v1"
+                        }
+                        .into(),
+                    ),
+                    kind: Some(workload_failure.kind.clone()),
+                    name: Some(workload_failure.name.clone()),
+                    namespace: Some(self.workload_namespace()),
+                    ..Default::default()
+                }),
+            )
+            .await;
+
+            self.fail(ctx, &workload_failure.reason, &message).await?;
+
+            return Ok(Action::await_change());
+        }
+
+        let is_created = match self.create_ready_workloads(ctx.k8s_client.clone()).await {
+            Ok(is_created) => is_created,
+            Err(err) => {
+                self.fail(ctx, "WorkloadStartFailed", &err.to_string())
+                    .await?;
+
+                return Ok(Action::await_change());
+            }
+        };
+
+        let requeue_after = match (is_created, remaining_time) {
+            (false, Some(remaining_time)) => remaining_time.min(READINESS_POLL_INTERVAL),
+            (false, None) => READINESS_POLL_INTERVAL,
+            (true, Some(remaining_time)) => remaining_time,
+            (true, None) => return Ok(Action::await_change()),
+        };
+
+        Ok(Action::requeue(requeue_after))
+    }
+
+    /// Removes the workloads of this Benchmark, marks it as Failed and moves the queue to the next Benchmark.
+    pub async fn fail(&self, ctx: Arc<Context>, reason: &str, message: &str) -> Result<(), Error> {
+        warn!("Failing Benchmark {}: {}", self.name_any(), message);
+
+        self.stop(ctx, BenchmarkState::Failed, reason, message)
+            .await
+    }
+
+    /// Removes the workloads of this Benchmark, marks it as Cancelled and moves the queue to the next Benchmark.
+    pub async fn cancel(&self, ctx: Arc<Context>) -> Result<(), Error> {
+        info!("Cancelling Benchmark {}.", self.name_any());
+
+        self.stop(
+            ctx,
+            BenchmarkState::Cancelled,
+            "Cancelled",
+            "The Benchmark was cancelled before it finished.",
+        )
+        .await
+    }
+
+    /// Removes the workloads of this Benchmark, moves it to the final `state` and moves the queue to the next Benchmark.
+    async fn stop(
         &self,
         ctx: Arc<Context>,
-        benchmark_api: &Api<Benchmark>,
-    ) -> Result<Action, Error> {
+        state: BenchmarkState,
+        reason: &str,
+        message: &str,
+    ) -> Result<(), Error> {
         let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
 
-        // Remove Helm installs (i.e., all the Helm installs in the namespace).
-        Benchmark::uninstall_helm_charts(&namespace)?;
+        // Keep the logs of the workloads, these explain why the Benchmark stopped.
+        self.save_logs(ctx.k8s_client.clone()).await;
 
-        if let Some(status_current) = &self.status {
-            // If a running Benchmark CR is being deleted, clear the shared state so a new Benchmark can start when this one is cleaned up.
-            if status_current.state == BenchmarkState::Running {
-                ctx.state_data.write().await.clear_state();
-            }
+        // Remove the workloads of the stopped Benchmark.
+        self.remove_workloads(ctx.k8s_client.clone()).await?;
 
-            // Reorder the queue position of the pending Benchmarks.
-            Benchmark::reorder_pending_benchmarks(benchmark_api, status_current.queue_position)
-                .await?;
+        let event_type = match state {
+            BenchmarkState::Failed => EventType::Warning,
+            _ => EventType::Normal,
+        };
+
+        // Change the status of this CR to its final state.
+        let state_name = format!("{state:?}");
+        let mut duration = None;
+        Benchmark::update_status(ctx.k8s_client.clone(), &name, &namespace, |status| {
+            status.finish(state, reason, message);
+            duration = status.duration();
+        })
+        .await?;
+
+        if let Some(duration) = duration {
+            ctx.metrics
+                .observe_benchmark_duration(&state_name, duration);
         }
 
+        self.publish_event(ctx.k8s_client.clone(), event_type, reason, message, None)
+            .await;
+
+        // Free the shared state so the next Benchmark can start.
+        ctx.state_data
+            .write()
+            .await
+            .clear_benchmark(&name, &namespace);
+
+        // Reorder Pending Benchmarks.
+        Benchmark::reorder_pending_benchmarks(ctx, &namespace, &name).await?;
+
+        Ok(())
+    }
+
+    /// Cleanup is called when a Benchmark CR get removed.
+    /// Because we make use of the Owner principle, any objects that are created by a Benchmark will be automatically removed by Kubernetes.
+    /// Helm releases and cluster-scoped manifests are not owned by the Benchmark, so the ones recorded in the status are removed.
+    /// See <https://kubernetes.io/docs/concepts/overview/working-with-objects/owners-dependents/> for more information.
+    pub async fn cleanup(&self, ctx: Arc<Context>) -> Result<Action, Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
+
+        self.publish_event(
+            ctx.k8s_client.clone(),
+            EventType::Normal,
+            "Cleanup",
+            "The Benchmark is deleted, removing its Helm releases and manifests.",
+            None,
+        )
+        .await;
+
+        // Remove the Helm releases of this Benchmark.
+        helm::uninstall(&self.workload_namespace(), &self.helm_releases()).await?;
+
+        // Remove the objects created from manifests, cluster-scoped objects are not owned by the Benchmark.
+        self.remove_manifests(ctx.k8s_client.clone()).await?;
+
+        // The namespace of an isolated Benchmark is not owned by the Benchmark either.
+        self.remove_workload_namespace(ctx.k8s_client.clone())
+            .await?;
+
+        // If an active Benchmark CR is being deleted, clear the shared state so a new Benchmark can start when this one is cleaned up.
+        ctx.state_data
+            .write()
+            .await
+            .clear_benchmark(&name, &namespace);
+
+        // Reorder the queue position of the pending Benchmarks (finished Benchmarks already left the queue).
+        Benchmark::reorder_pending_benchmarks(ctx, &namespace, &name).await?;
+
         Ok(Action::await_change())
     }
 
-    /// Set a new Benchmark state keeping other status properties intact.
-    pub async fn set_benchmark_state(
+    /// Moves the Benchmark from the state `from` to the state `to`, keeping other status properties intact.
+    /// Returns whether the Benchmark was in the state `from`, its state is left unchanged otherwise.
+    /// The update is guarded by the resource version, so a concurrent state change (e.g., a timeout) is not overwritten.
+    pub async fn transition_state(
         k8s_client: Client,
         benchmark_name: &str,
         namespace: &str,
-        state: BenchmarkState,
+        from: BenchmarkState,
+        to: BenchmarkState,
+    ) -> Result<bool, Error> {
+        let benchmark_api = Api::<Benchmark>::namespaced(k8s_client, namespace);
+
+        loop {
+            let mut benchmark = benchmark_api.get_status(benchmark_name).await?;
+
+            let Some(status) = benchmark.status.as_mut() else {
+                return Ok(false);
+            };
+
+            if status.state != from {
+                return Ok(false);
+            }
+
+            status.state = to.clone();
+
+            match benchmark_api
+                .replace_status(
+                    benchmark_name,
+                    &PostParams::default(),
+                    serde_json::to_vec(&benchmark).map_err(Error::SerdeError)?,
+                )
+                .await
+            {
+                Ok(_) => return Ok(true),
+                // Updated in the meantime, check the state again.
+                Err(Error::Api(err)) if err.code == 409 => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Update the current status of a Benchmark with `update`, keeping the other status properties intact.
+    pub async fn update_status(
+        k8s_client: Client,
+        benchmark_name: &str,
+        namespace: &str,
+        update: impl FnOnce(&mut BenchmarkStatus),
     ) -> Result<(), Error> {
         let benchmark_api = Api::<Benchmark>::namespaced(k8s_client, namespace);
 
         let current_status = benchmark_api.get_status(benchmark_name).await?.status;
 
         if let Some(mut current_status) = current_status {
-            current_status.state = state;
+            update(&mut current_status);
 
             let status = json!({ "status": current_status });
 
@@ -280,110 +903,37 @@ impl Benchmark {
         } else {
             Err(Error::Api(ErrorResponse {
                 status: "Error".into(),
-                message: "Could not set Benchmark state".into(),
+                message: "Could not update Benchmark status".into(),
                 reason: "Current status is None".into(),
                 code: 404,
             }))
         }
     }
 
-    /// Decrement the queue position of all the pending benchmarks which have a queue position > `current_queue_pos`.
+    /// Removes the Benchmark from the cluster-wide queue and updates the queue position of the pending benchmarks.
+    /// The relative order of the pending benchmarks, which follows their priority, is preserved.
     pub async fn reorder_pending_benchmarks(
-        benchmark_api: &Api<Benchmark>,
-        current_queue_pos: u32,
+        ctx: Arc<Context>,
+        namespace: &str,
+        benchmark_name: &str,
     ) -> Result<(), Error> {
-        let mut pending_benchmarks = Vec::<Benchmark>::new();
-
-        // Store pending benchmarks if their queue position is larger than the benchmark that is being deleted or has state `done`.
-        for bench_iter in benchmark_api.list(&ListParams::default()).await?.iter() {
-            if let Some(status_iter) = &bench_iter.status {
-                if status_iter.state == BenchmarkState::Pending
-                    && status_iter.queue_position > current_queue_pos
-                {
-                    let mut pending_bench = bench_iter.clone();
-
-                    // Decrement the queue position.
-                    // Unwrap is safe, because the optional status of the benchmark was already checked.
-                    pending_bench.status.as_mut().unwrap().queue_position -= 1;
-
-                    // Store the pending benchmark.
-                    pending_benchmarks.push(pending_bench);
-                }
-            }
-        }
-
-        // Update the benchmarks CRs with a new status.
-        // rev() to make sure we update the largest queue position first and the lowest last,
-        // to prevent that a benchmark gets started while the queue was not completely updated.
-        for bench_iter in pending_benchmarks.iter().rev() {
-            let new_status = json!({ "status": bench_iter.status.clone().unwrap() });
-
-            benchmark_api
-                .patch_status(
-                    &bench_iter.name_any(),
-                    &PatchParams::default(),
-                    &Patch::Merge(&new_status),
-                )
-                .await?;
-        }
-
-        Ok(())
-    }
-
-    /// Uninstalls all the Helm charts from the provided namespace.
-    pub fn uninstall_helm_charts(namespace: &str) -> Result<(), Error> {
-        // helm ls -n benchmarking --all --short | xargs -r helm delete -n benchmarking
-        let helm_list_stdout = Command::new("helm")
-            .args(["ls", "-n", namespace, "--all", "--short"])
-            .stdout(Stdio::piped())
-            .spawn()
-            .map_err(|_| {
-                Error::Api(ErrorResponse {
-                    status: "Error".into(),
-                    message: "Helm list failed.".into(),
-                    reason: "Helm list command failed.".into(),
-                    code: 500,
-                })
-            })?
-            .stdout
-            .ok_or(Error::Api(ErrorResponse {
-                status: "Error".into(),
-                message: "Failed to open Helm list stdout.".into(),
-                reason: "Failed to open Helm list stdout.".into(),
-                code: 500,
-            }))?;
-
-        Command::new("xargs")
-            .args(["-r", "helm", "delete", "-n", namespace])
-            .stdin(Stdio::from(helm_list_stdout))
-            .spawn()
-            .map_err(|_| {
-                Error::Api(ErrorResponse {
-                    status: "Error".into(),
-                    message: "Helm delete failed.".into(),
-                    reason: "Helm delete command failed.".into(),
-                    code: 500,
-                })
-            })?;
+        ctx.queue.remove(namespace, benchmark_name).await?;
 
-        Ok(())
+        ctx.queue.sync_queue_positions().await
     }
 }
 
-/// Entry-point to start the controller.
-pub async fn run(state: &State) {
-    let k8s_client = Client::try_default()
-        .await
-        .expect("Failed to create kube client.");
+/// Installs the CRD when it is not installed yet and updates it otherwise, exits when the CRD can't be installed.
+pub async fn install_crd(k8s_client: Client, crd: CustomResourceDefinition) {
+    let crd_name = crd.name_any();
 
     // Cluster level access to `CustomResourceDefinition` resources.
-    let crd_api = Api::<CustomResourceDefinition>::all(k8s_client.clone());
+    let crd_api = Api::<CustomResourceDefinition>::all(k8s_client);
 
-    // Check if the CRD is installed.
-    match crd_api.get_opt(Benchmark::crd_name()).await {
+    match crd_api.get_opt(&crd_name).await {
         Ok(None) => {
-            // The Benchmark CRD does not exist.
-            warn!("Benchmark CRD is not queryable. Is the CRD installed?");
+            // The CRD does not exist.
+            warn!("{} CRD is not queryable. Is the CRD installed?", crd_name);
 
             /*
             It is not advisable to install the CRD from inside the controller when developing an operator that runs on a production cluster.
@@ -395,10 +945,7 @@ pub async fn run(state: &State) {
             info!("Trying to install the CRD.");
 
             // Try to create the CRD, exit on fail.
-            if let Err(err) = crd_api
-                .create(&PostParams::default(), &Benchmark::crd())
-                .await
-            {
+            if let Err(err) = crd_api.create(&PostParams::default(), &crd).await {
                 error!("Error creating the CRD: {err:#?}");
                 info!("Install the CRD manually: cargo run --bin generate_crd && kubectl apply -f crd.yaml");
                 exit(1);
@@ -407,12 +954,8 @@ pub async fn run(state: &State) {
             info!("CRD resource created, waiting until it is available...");
 
             // Wait until CRD is available.
-            if let Err(err) = await_condition(
-                crd_api,
-                Benchmark::crd_name(),
-                conditions::is_crd_established(),
-            )
-            .await
+            if let Err(err) =
+                await_condition(crd_api, &crd_name, conditions::is_crd_established()).await
             {
                 error!("Error waiting on CRD establishment: {err:#?}");
                 info!("Manually check if the CRD was installed. If not, install the CRD manually: cargo run --bin generate_crd && kubectl apply -f crd.yaml");
@@ -421,20 +964,89 @@ pub async fn run(state: &State) {
 
             info!("CRD successfully installed.");
         }
-        Ok(Some(_)) => info!("CRD is present."),
+        Ok(Some(_)) => {
+            // Always apply the generated CRD, a schema change within an existing version would otherwise never reach the cluster
+            // and the apiserver would prune the new fields. Applying an unchanged CRD changes nothing, the stored objects are kept.
+            info!("CRD is present, applying the generated CRD.");
+
+            if let Err(err) = crd_api
+                .patch(
+                    &crd_name,
+                    &PatchParams::apply("benchmark-operator").force(),
+                    &Patch::Apply(&crd),
+                )
+                .await
+            {
+                error!("Error updating the CRD: {err:#?}");
+                info!("Update the CRD manually: cargo run --bin generate_crd && kubectl apply -f crd.yaml");
+                exit(1);
+            }
+
+            info!("CRD is up to date.");
+        }
         Err(err) => {
             error!("Could not query CRD resources: {err:#?}.");
             exit(1);
         }
     }
+}
+
+/// Entry-point to start the controller.
+pub async fn run(state: &State) {
+    let k8s_client = Client::try_default()
+        .await
+        .expect("Failed to create kube client.");
+
+    // Check if the CRD is installed.
+    install_crd(k8s_client.clone(), benchmark_crd()).await;
+
+    // Benchmarks stored in an older version would block removing that version from the CRD.
+    if let Err(err) = migrate_storage_version(k8s_client.clone()).await {
+        warn!("Could not migrate the stored Benchmarks to the storage version: {err:#?}");
+    }
 
     // Cluster level access to `Benchmark` resources.
     let benchmark_api = Api::<Benchmark>::all(k8s_client.clone());
 
+    let ctx = state.create_context(k8s_client);
+
+    // Rebuild the shared state from the cluster, in case the operator restarted while a Benchmark was active.
+    if let Err(err) = restore_state(ctx.clone(), &benchmark_api).await {
+        error!("Could not restore the operator state: {err:#?}");
+        exit(1);
+    }
+
     // Initializing and running the controller.
-    Controller::new(benchmark_api, watcher::Config::default())
+    let controller = Controller::new(benchmark_api, watcher::Config::default());
+    let benchmark_store = controller.store();
+
+    // The metrics read the Benchmarks from the cache of the controller, instead of listing them on every scrape.
+    let _ = state.benchmark_store.set(benchmark_store.clone());
+
+    // Watch the workload Pods, so a failing workload fails its Benchmark right away.
+    // The Pods of Deployments, StatefulSets and Jobs are not owned by the Benchmark itself,
+    // so the Pods are mapped to their Benchmark by the UID label instead of the owner references.
+    controller
+        // Watch the runs of the matrix Benchmarks, so the states of the runs are aggregated in their matrix Benchmark.
+        .owns(
+            Api::<Benchmark>::all(ctx.k8s_client.clone()),
+            watcher::Config::default().labels(MATRIX_PARENT_LABEL),
+        )
+        .watches(
+            Api::<Pod>::all(ctx.k8s_client.clone()),
+            watcher::Config::default().labels(BENCHMARK_UID_LABEL),
+            move |pod| {
+                let benchmark_uid = pod.labels().get(BENCHMARK_UID_LABEL).cloned();
+
+                benchmark_store
+                    .state()
+                    .into_iter()
+                    .filter(move |benchmark| benchmark.uid() == benchmark_uid)
+                    .map(|benchmark| ObjectRef::from_obj(benchmark.as_ref()))
+            },
+        )
         .shutdown_on_signal()
-        .run(reconcile, error_policy, state.create_context(k8s_client))
+        .run(reconcile, error_policy, ctx)
         .for_each(|res| async move {
             match res {
                 Ok(o) => info!("reconciled {o:?}"),
@@ -444,15 +1056,92 @@ pub async fn run(state: &State) {
         .await;
 }
 
+/// Rebuilds the shared state from the existing Benchmark CRs and their Pods.
+/// An active Benchmark (i.e., its workloads are started) is resumed when its Pods are still alive,
+/// otherwise it is failed on purpose so the queue does not get stuck.
+async fn restore_state(ctx: Arc<Context>, benchmark_api: &Api<Benchmark>) -> Result<(), Error> {
+    let mut is_resumed = false;
+
+    let benchmarks = benchmark_api.list(&ListParams::default()).await?.items;
+
+    // Create the cluster-wide queue, in case the operator was upgraded from a version with per namespace queues,
+    // or drop the entries of the Benchmarks that were removed while the operator was down.
+    ctx.queue.init(&benchmarks).await?;
+
+    for benchmark in benchmarks.iter() {
+        let Some(status) = &benchmark.status else {
+            continue;
+        };
+
+        let is_active = status.state == BenchmarkState::Running
+            || (status.state == BenchmarkState::Pending && status.started_at.is_some());
+
+        if !is_active {
+            continue;
+        }
+
+        let namespace = benchmark.namespace().unwrap_or("default".to_string());
+        let name = benchmark.name_any();
+
+        let pods_api =
+            Api::<Pod>::namespaced(ctx.k8s_client.clone(), &benchmark.workload_namespace());
+
+        // Only Benchmarks with Pod workloads can be checked, Helm installs and manifests are not labelled Pods.
+        let has_pod_workloads = benchmark
+            .spec
+            .workloads
+            .iter()
+            .any(BenchmarkWorkload::has_pods);
+        let has_alive_pods = benchmark.owned_pods(&pods_api).await?.iter().any(|pod| {
+            matches!(
+                pod.status
+                    .as_ref()
+                    .and_then(|status| status.phase.as_deref()),
+                Some("Pending" | "Running")
+            )
+        });
+
+        if is_resumed {
+            // Only one Benchmark can be active at a time.
+            benchmark
+                .fail(
+                    ctx.clone(),
+                    "OperatorRestarted",
+                    "Another Benchmark was already active when the operator restarted.",
+                )
+                .await?;
+        } else if has_pod_workloads && !has_alive_pods {
+            benchmark
+                .fail(
+                    ctx.clone(),
+                    "OperatorRestarted",
+                    "The workloads of the Benchmark were gone when the operator restarted.",
+                )
+                .await?;
+        } else {
+            info!("Resuming Benchmark {} after operator restart.", name);
+
+            let mut state_data = ctx.state_data.write().await;
+            state_data.new_benchmark(name, namespace);
+            state_data.is_benchmark_running = status.state == BenchmarkState::Running;
+
+            is_resumed = true;
+        }
+    }
+
+    Ok(())
+}
+
 async fn reconcile(
     benchmark: Arc<Benchmark>,
     ctx: Arc<Context>,
 ) -> Result<Action, finalizer::Error<Error>> {
+    ctx.metrics.count_reconciliation("benchmark");
+
     let namespace = benchmark.namespace().unwrap_or("default".to_string());
 
     // Apis.
     let benchmark_api = Api::<Benchmark>::namespaced(ctx.k8s_client.clone(), &namespace);
-    let pods_api = Api::<Pod>::namespaced(ctx.k8s_client.clone(), &namespace);
 
     finalizer(
         &benchmark_api,
@@ -461,11 +1150,9 @@ async fn reconcile(
         |event| async {
             match event {
                 finalizer::Event::Apply(benchmark) => {
-                    benchmark.reconcile(ctx, &benchmark_api, &pods_api).await
-                }
-                finalizer::Event::Cleanup(benchmark) => {
-                    benchmark.cleanup(ctx, &benchmark_api).await
+                    benchmark.reconcile(ctx, &benchmark_api).await
                 }
+                finalizer::Event::Cleanup(benchmark) => benchmark.cleanup(ctx).await,
             }
         },
     )
@@ -475,7 +1162,132 @@ async fn reconcile(
 fn error_policy(
     _benchmark: Arc<Benchmark>,
     _error: &finalizer::Error<Error>,
-    _ctx: Arc<Context>,
+    ctx: Arc<Context>,
 ) -> Action {
+    ctx.metrics.count_reconcile_error("benchmark");
+
     Action::requeue(Duration::from_secs(60))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn benchmark(timeout_seconds: Option<u64>, started_at: Option<DateTime<Utc>>) -> Benchmark {
+        let mut benchmark = Benchmark::new(
+            "benchmark",
+            BenchmarkSpec {
+                timeout_seconds,
+                ..Default::default()
+            },
+        );
+
+        benchmark.status = Some(BenchmarkStatus {
+            started_at: started_at.map(Time),
+            ..Default::default()
+        });
+
+        benchmark
+    }
+
+    #[test]
+    fn finish_sets_final_state_and_condition() {
+        let mut status = BenchmarkStatus::default();
+        status.start("run".into());
+        status.finish(
+            BenchmarkState::Failed,
+            "DeadlineExceeded",
+            "Benchmark exceeded its timeout.",
+        );
+
+        assert_eq!(status.state, BenchmarkState::Failed);
+        assert!(status.finished_at.is_some());
+
+        let condition = status.finished_condition().unwrap();
+        assert_eq!(condition.status, "True");
+        assert_eq!(condition.reason, "DeadlineExceeded");
+    }
+
+    #[test]
+    fn duration_is_time_between_start_and_finish() {
+        let started_at = Utc::now();
+        let status = BenchmarkStatus {
+            started_at: Some(Time(started_at)),
+            finished_at: Some(Time(started_at + chrono::Duration::seconds(90))),
+            ..Default::default()
+        };
+
+        assert_eq!(status.duration(), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn duration_is_none_without_start() {
+        let status = BenchmarkStatus {
+            finished_at: Some(Time(Utc::now())),
+            ..Default::default()
+        };
+
+        assert_eq!(status.duration(), None);
+    }
+
+    #[test]
+    fn remaining_time_is_none_without_timeout() {
+        assert_eq!(benchmark(None, Some(Utc::now())).remaining_time(), None);
+    }
+
+    #[test]
+    fn remaining_time_is_zero_when_timeout_exceeded() {
+        let started_at = Utc::now() - chrono::Duration::seconds(120);
+
+        assert_eq!(
+            benchmark(Some(60), Some(started_at)).remaining_time(),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn remaining_time_counts_from_start() {
+        let started_at = Utc::now() - chrono::Duration::seconds(20);
+        let remaining_time = benchmark(Some(60), Some(started_at))
+            .remaining_time()
+            .unwrap();
+
+        assert!(remaining_time <= Duration::from_secs(40));
+        assert!(remaining_time > Duration::from_secs(30));
+    }
+
+    #[test]
+    fn remaining_time_is_full_timeout_without_start() {
+        assert_eq!(
+            benchmark(Some(60), None).remaining_time(),
+            Some(Duration::from_secs(60))
+        );
+
+        let mut benchmark = benchmark(Some(60), None);
+        benchmark.status = None;
+        assert_eq!(benchmark.remaining_time(), Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn child_name_appends_suffix() {
+        assert_eq!(
+            child_name("benchmark", "-0", MAX_NAME_LENGTH),
+            "benchmark-0"
+        );
+    }
+
+    #[test]
+    fn child_name_truncates_name_and_keeps_suffix() {
+        let name = child_name(&"a".repeat(300), "-12", MAX_NAME_LENGTH);
+
+        assert_eq!(name.len(), MAX_NAME_LENGTH);
+        assert!(name.ends_with("a-12"));
+        assert_eq!(child_name("benchmark", "-1", 6), "benc-1");
+    }
+
+    #[test]
+    fn child_name_trims_separators_before_suffix() {
+        assert_eq!(child_name("bench-.mark", "-1", 8), "bench-1");
+    }
+}
@@ -1,11 +1,19 @@
-use benchmark_operator::benchmark_controller::Benchmark;
+use benchmark_operator::{
+    crd_versions::benchmark_crd, schedule_controller::BenchmarkSchedule,
+    suite_controller::BenchmarkSuite,
+};
 use kube::CustomResourceExt;
 
-/// Generates the CustomResourceDefinition and writes to `crd.yaml`
+/// Generates the CustomResourceDefinitions and writes them to `crd.yaml`
 fn main() -> std::io::Result<()> {
     std::fs::write(
         "crd.yaml",
-        serde_yaml::to_string(&Benchmark::crd()).unwrap(),
+        format!(
+            "{}---\n{}---\n{}",
+            serde_yaml::to_string(&benchmark_crd()).unwrap(),
+            serde_yaml::to_string(&BenchmarkSchedule::crd()).unwrap(),
+            serde_yaml::to_string(&BenchmarkSuite::crd()).unwrap()
+        ),
     )?;
 
     Ok(())
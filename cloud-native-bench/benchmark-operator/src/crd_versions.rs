@@ -0,0 +1,162 @@
+use crate::benchmark_controller::Benchmark;
+use k8s_openapi::apiextensions_apiserver::pkg::apis::apiextensions::v1::CustomResourceDefinition;
+use kube::{
+    api::{ApiResource, DynamicObject, ListParams, Patch, PatchParams, PostParams},
+    core::crd::merge_crds,
+    Api, Client, CustomResourceExt, Error, Resource, ResourceExt,
+};
+use serde_json::json;
+use tracing::info;
+
+/// Benchmark CRD v1, the top-level fields of its spec are fixed.
+/// The workload and status types are shared with the storage version, so they only change in ways every version accepts
+/// (i.e., new optional fields), see `benchmark_crd`.
+pub mod v1 {
+    use crate::{
+        benchmark_controller::{
+            workloads_schema, BenchmarkStatus, BenchmarkType, BenchmarkWorkload,
+        },
+        matrix::matrix_schema,
+    };
+    use kube::CustomResource;
+    use schemars::JsonSchema;
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    /// Benchmark CRD spec v1, without the fields that were added in later versions, so existing manifests and clients keep working.
+    #[derive(CustomResource, Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
+    #[kube(
+        group = "michiel.van.kenhove.ugent.be",
+        version = "v1",
+        kind = "Benchmark",
+        status = "BenchmarkStatus",
+        shortname = "bench",
+        category = "all",
+        printcolumn = r#"{"name": "State", "type": "string", "jsonPath": ".status.state"}"#,
+        printcolumn = r#"{"name": "Queue Position", "type": "integer", "jsonPath": ".status.queuePosition"}"#,
+        printcolumn = r#"{"name": "Priority", "type": "integer", "jsonPath": ".spec.priority", "priority": 1}"#,
+        printcolumn = r#"{"name": "Reason", "type": "string", "jsonPath": ".status.conditions[?(@.type==\"Finished\")].reason", "priority": 1}"#,
+        printcolumn = r#"{"name": "Started", "type": "date", "jsonPath": ".status.startedAt", "priority": 1}"#,
+        printcolumn = r#"{"name": "Finished", "type": "date", "jsonPath": ".status.finishedAt", "priority": 1}"#,
+        printcolumn = r#"{"name": "Age", "type": "date", "jsonPath": ".metadata.creationTimestamp"}"#,
+        namespaced
+    )]
+    #[serde(rename_all = "camelCase")]
+    pub struct BenchmarkSpec {
+        pub title: String,
+        pub benchmark_type: BenchmarkType,
+        #[schemars(schema_with = "workloads_schema")]
+        pub workloads: Vec<BenchmarkWorkload>,
+        /// Maximum time the Benchmark may take, counted from the moment its workloads are started.
+        /// When exceeded, the workloads are removed and the Benchmark is marked `Failed`. At most 7 days.
+        #[schemars(range(min = 1, max = 604800))]
+        pub timeout_seconds: Option<u64>,
+        /// Pending Benchmarks with a higher priority are started before Pending Benchmarks with a lower priority.
+        /// Benchmarks with the same priority are started in the order they were created, defaults to 0.
+        pub priority: Option<i32>,
+        /// Cancels the Benchmark when set, its workloads are removed and it is marked `Cancelled`.
+        /// Unlike deleting the Benchmark, the CR (and its status) is kept.
+        #[serde(default)]
+        pub cancel: bool,
+        /// Parameters to run the Benchmark with, the Benchmark is expanded into one run (a child Benchmark) per combination.
+        /// `{{ matrix.<key> }}` in the title and the workloads is replaced by the value of the parameter in the run.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        #[schemars(schema_with = "matrix_schema")]
+        pub matrix: Option<BTreeMap<String, Vec<String>>>,
+    }
+}
+
+/// Benchmark CRD with all its served versions, the version of `Benchmark` is the storage version.
+/// The versions share the stored objects without conversion (i.e., conversion strategy `None`), which requires
+/// every older version to be a subset of the storage version: a new version may only add optional fields.
+/// A change that renames or restructures fields needs a conversion webhook.
+/// The older versions are marked deprecated, so `kubectl` warns when they are used.
+pub fn benchmark_crd() -> CustomResourceDefinition {
+    let storage_version = Benchmark::version(&());
+
+    let mut crd = merge_crds(
+        vec![v1::Benchmark::crd(), Benchmark::crd()],
+        &storage_version,
+    )
+    .expect("The versions of the Benchmark CRD are incompatible.");
+
+    for version in crd.spec.versions.iter_mut() {
+        if version.name != storage_version {
+            version.deprecated = Some(true);
+            version.deprecation_warning = Some(format!(
+                "{}/{} Benchmark is deprecated, use {}.",
+                Benchmark::group(&()),
+                version.name,
+                Benchmark::api_version(&())
+            ));
+        }
+    }
+
+    crd
+}
+
+/// Rewrites the stored Benchmarks in the storage version, after which the older versions are dropped from the stored versions of the CRD.
+/// Once no objects are stored in an older version, that version can be removed from the CRD without losing the Benchmark history.
+pub async fn migrate_storage_version(k8s_client: Client) -> Result<(), Error> {
+    let storage_version = Benchmark::version(&());
+
+    let crd_api = Api::<CustomResourceDefinition>::all(k8s_client.clone());
+    let crd = crd_api.get(Benchmark::crd_name()).await?;
+
+    let stored_versions = crd
+        .status
+        .and_then(|status| status.stored_versions)
+        .unwrap_or_default();
+
+    if stored_versions
+        .iter()
+        .all(|version| *version == storage_version)
+    {
+        return Ok(());
+    }
+
+    info!(
+        "Migrating the stored Benchmarks from {:?} to {}.",
+        stored_versions, storage_version
+    );
+
+    // Dynamic objects, so the objects are written back exactly as they were read.
+    let api_resource = ApiResource::erase::<Benchmark>(&());
+
+    let benchmarks = Api::<DynamicObject>::all_with(k8s_client.clone(), &api_resource)
+        .list(&ListParams::default())
+        .await?
+        .items;
+
+    for benchmark in benchmarks {
+        let benchmark_api = Api::<DynamicObject>::namespaced_with(
+            k8s_client.clone(),
+            &benchmark.namespace().unwrap_or("default".to_string()),
+            &api_resource,
+        );
+
+        // An update without changes stores the object in the storage version.
+        match benchmark_api
+            .replace(&benchmark.name_any(), &PostParams::default(), &benchmark)
+            .await
+        {
+            // Removed in the meantime, or updated in the meantime (which stored it in the storage version).
+            Err(Error::Api(err)) if err.code == 404 || err.code == 409 => {}
+            result => {
+                result?;
+            }
+        }
+    }
+
+    crd_api
+        .patch_status(
+            Benchmark::crd_name(),
+            &PatchParams::default(),
+            &Patch::Merge(&json!({ "status": { "storedVersions": [storage_version] } })),
+        )
+        .await?;
+
+    info!("Stored Benchmarks migrated to {}.", storage_version);
+
+    Ok(())
+}
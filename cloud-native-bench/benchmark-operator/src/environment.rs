@@ -0,0 +1,230 @@
+use crate::benchmark_controller::Benchmark;
+use benchmark_adapter_types::WorkloadEnvironment;
+use benchmark_database_interface::DatabaseInterface;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::{Api, Client, Error, ResourceExt};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+};
+use tracing::warn;
+
+/// Labels of Node Feature Discovery that identify the CPU model of a node.
+const CPU_VENDOR_LABEL: &str = "feature.node.kubernetes.io/cpu-model.vendor_id";
+const CPU_FAMILY_LABEL: &str = "feature.node.kubernetes.io/cpu-model.family";
+const CPU_MODEL_LABEL: &str = "feature.node.kubernetes.io/cpu-model.id";
+
+/// Capturing the environment the workloads of a Benchmark run in, so results of different runs can be compared fairly.
+impl Benchmark {
+    /// Collects the environment of every scheduled workload Pod of this Benchmark: the node it runs on,
+    /// the versions of the cluster and the node, the allocatable resources of the node, its labels and the image digests of the Pod.
+    pub async fn collect_environment(
+        &self,
+        k8s_client: Client,
+    ) -> Result<Vec<WorkloadEnvironment>, Error> {
+        let pods_api = Api::<Pod>::namespaced(k8s_client.clone(), &self.workload_namespace());
+        let nodes_api = Api::<Node>::all(k8s_client.clone());
+
+        let kubernetes_version = k8s_client.apiserver_version().await?.git_version;
+
+        let mut nodes: HashMap<String, Node> = HashMap::new();
+        let mut workload_environments = Vec::new();
+
+        for pod in self.owned_pods(&pods_api).await? {
+            let Some(node_name) = pod.spec.as_ref().and_then(|spec| spec.node_name.clone()) else {
+                continue;
+            };
+
+            if !nodes.contains_key(&node_name) {
+                nodes.insert(node_name.clone(), nodes_api.get(&node_name).await?);
+            }
+
+            let node = &nodes[&node_name];
+            let node_info = node
+                .status
+                .as_ref()
+                .and_then(|status| status.node_info.clone())
+                .unwrap_or_default();
+            let allocatable = node
+                .status
+                .as_ref()
+                .and_then(|status| status.allocatable.clone())
+                .unwrap_or_default();
+
+            workload_environments.push(WorkloadEnvironment {
+                benchmark_name: self.name_any(),
+                namespace: pod.namespace().unwrap_or_default(),
+                pod: pod.name_any(),
+                node: node_name.clone(),
+                kubernetes_version: kubernetes_version.clone(),
+                kubelet_version: node_info.kubelet_version,
+                container_runtime_version: node_info.container_runtime_version,
+                kernel_version: node_info.kernel_version,
+                os_image: node_info.os_image,
+                architecture: node_info.architecture,
+                cpu_model: cpu_model(node.labels()),
+                allocatable_cpu: allocatable.get("cpu").map(|quantity| quantity.0.clone()),
+                allocatable_memory: allocatable.get("memory").map(|quantity| quantity.0.clone()),
+                node_labels: serde_json::to_string(node.labels()).unwrap_or_default(),
+                image_digests: serde_json::to_string(&image_digests(&pod)).unwrap_or_default(),
+            });
+        }
+
+        Ok(workload_environments)
+    }
+
+    /// Saves the environment of the workload Pods of this Benchmark under its run.
+    /// The environment only describes the results, so failing to save it does not fail the Benchmark.
+    pub async fn save_environment(&self, k8s_client: Client) {
+        let name = self.name_any();
+
+        let Some(run_id) = self
+            .status
+            .as_ref()
+            .and_then(|status| status.run_id.clone())
+        else {
+            return;
+        };
+
+        let workload_environments = match self.collect_environment(k8s_client).await {
+            Ok(workload_environments) if workload_environments.is_empty() => return,
+            Ok(workload_environments) => workload_environments,
+            Err(err) => {
+                warn!(
+                    "Could not collect the environment of Benchmark {}: {}",
+                    name, err
+                );
+                return;
+            }
+        };
+
+        let Ok(database_url) = env::var("DATABASE_URL") else {
+            warn!("DATABASE_URL environment variable was not set, the environment of Benchmark {} is not saved.", name);
+            return;
+        };
+
+        if let Err(err) =
+            DatabaseInterface::save_environment(database_url, run_id, workload_environments).await
+        {
+            warn!(
+                "Could not save the environment of Benchmark {}: {}",
+                name, err
+            );
+        }
+    }
+}
+
+/// CPU model of a node labeled by Node Feature Discovery (e.g., `Intel family 6 model 85`).
+fn cpu_model(labels: &BTreeMap<String, String>) -> Option<String> {
+    let vendor = labels.get(CPU_VENDOR_LABEL)?;
+
+    match (labels.get(CPU_FAMILY_LABEL), labels.get(CPU_MODEL_LABEL)) {
+        (Some(family), Some(model)) => Some(format!("{vendor} family {family} model {model}")),
+        _ => Some(vendor.clone()),
+    }
+}
+
+/// Digest of the image of every container of the Pod (including init containers), keyed by the container name.
+fn image_digests(pod: &Pod) -> BTreeMap<String, String> {
+    pod.status
+        .iter()
+        .flat_map(|status| {
+            status
+                .init_container_statuses
+                .iter()
+                .flatten()
+                .chain(status.container_statuses.iter().flatten())
+        })
+        .map(|container_status| {
+            (
+                container_status.name.clone(),
+                container_status.image_id.clone(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(labels: serde_json::Value) -> Node {
+        serde_json::from_value(json!({
+            "metadata": { "name": "node-1", "labels": labels },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn cpu_model_combines_vendor_family_and_model() {
+        let node = node(json!({
+            "kubernetes.io/hostname": "node-1",
+            CPU_VENDOR_LABEL: "Intel",
+            CPU_FAMILY_LABEL: "6",
+            CPU_MODEL_LABEL: "85",
+        }));
+
+        assert_eq!(
+            cpu_model(node.labels()).as_deref(),
+            Some("Intel family 6 model 85")
+        );
+    }
+
+    #[test]
+    fn cpu_model_falls_back_to_vendor() {
+        let node = node(json!({ CPU_VENDOR_LABEL: "AMD" }));
+
+        assert_eq!(cpu_model(node.labels()).as_deref(), Some("AMD"));
+    }
+
+    #[test]
+    fn cpu_model_is_unknown_without_node_feature_discovery() {
+        let node = node(json!({ "kubernetes.io/hostname": "node-1" }));
+
+        assert_eq!(cpu_model(node.labels()), None);
+    }
+
+    #[test]
+    fn image_digests_include_init_containers() {
+        let pod: Pod = serde_json::from_value(json!({
+            "metadata": { "name": "workload-0" },
+            "status": {
+                "initContainerStatuses": [{
+                    "name": "init",
+                    "image": "busybox:1.36",
+                    "imageID": "docker.io/library/busybox@sha256:1111",
+                    "ready": true,
+                    "restartCount": 0,
+                }],
+                "containerStatuses": [{
+                    "name": "server",
+                    "image": "nginx:1.25",
+                    "imageID": "docker.io/library/nginx@sha256:2222",
+                    "ready": true,
+                    "restartCount": 0,
+                }],
+            },
+        }))
+        .unwrap();
+
+        assert_eq!(
+            image_digests(&pod),
+            BTreeMap::from([
+                (
+                    "init".to_string(),
+                    "docker.io/library/busybox@sha256:1111".to_string()
+                ),
+                (
+                    "server".to_string(),
+                    "docker.io/library/nginx@sha256:2222".to_string()
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn image_digests_are_empty_without_status() {
+        assert!(image_digests(&Pod::default()).is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+use crate::benchmark_controller::Benchmark;
+use k8s_openapi::api::core::v1::ObjectReference;
+use kube::{
+    runtime::events::{Event, EventType, Recorder, Reporter},
+    Client, Resource,
+};
+use std::env;
+use tracing::warn;
+
+/// Maximum length of the note of an Event, longer notes are rejected by the API server.
+const MAX_NOTE_LENGTH: usize = 1024;
+
+/// Publishing Kubernetes Events, so the lifecycle of a Benchmark shows up in `kubectl describe bench`.
+impl Benchmark {
+    /// Publishes an Event about this Benchmark, `related` refers to the object involved (e.g., a workload Pod).
+    /// Events are informational, so a failure to publish an Event only logs a warning.
+    pub async fn publish_event(
+        &self,
+        k8s_client: Client,
+        type_: EventType,
+        reason: &str,
+        note: &str,
+        related: Option<ObjectReference>,
+    ) {
+        publish_event(
+            k8s_client,
+            self.object_ref(&()),
+            type_,
+            reason,
+            note,
+            related,
+        )
+        .await
+    }
+}
+
+/// Publishes an Event about the object `regarding` (e.g., a BenchmarkSchedule), a failure to publish an Event only logs a warning.
+pub async fn publish_event(
+    k8s_client: Client,
+    regarding: ObjectReference,
+    type_: EventType,
+    reason: &str,
+    note: &str,
+    related: Option<ObjectReference>,
+) {
+    let kind = regarding.kind.clone().unwrap_or_default();
+    let name = regarding.name.clone().unwrap_or_default();
+
+    let recorder = Recorder::new(k8s_client, reporter(), regarding);
+
+    let event = Event {
+        type_,
+        reason: reason.into(),
+        note: Some(note.chars().take(MAX_NOTE_LENGTH).collect()),
+        action: "Reconcile".into(),
+        secondary: related,
+    };
+
+    if let Err(err) = recorder.publish(event).await {
+        warn!(
+            "Could not publish {} Event of {} {}: {}",
+            reason, kind, name, err
+        );
+    }
+}
+
+/// The operator replica that publishes the Events, the name of the Pod is provided by the downward API.
+fn reporter() -> Reporter {
+    Reporter {
+        controller: "benchmark-operator".into(),
+        instance: env::var("POD_NAME").or_else(|_| env::var("HOSTNAME")).ok(),
+    }
+}
@@ -8,8 +8,8 @@ use benchmark_proto::protos::benchmark::{
 };
 use chrono::Utc;
 use futures::FutureExt;
-use kube::Client;
-use std::env;
+use kube::{runtime::events::EventType, Api, Client};
+use std::{env, time::Instant};
 use tonic::{transport::Server, Code, Request, Response, Status};
 use tracing::info;
 
@@ -29,11 +29,20 @@ impl BenchmarkGrpcService {
             k8s_client,
         }
     }
-}
 
-#[tonic::async_trait]
-impl BenchmarkService for BenchmarkGrpcService {
-    async fn benchmark_started(
+    /// Records the outcome and the latency of a call of `method` in the metrics.
+    fn observe_call<T>(&self, method: &str, result: &Result<T, Status>, start: Instant) {
+        let code = match result {
+            Ok(_) => Code::Ok,
+            Err(status) => status.code(),
+        };
+
+        self.state
+            .metrics
+            .observe_grpc_call(method, &format!("{code:?}"), start.elapsed());
+    }
+
+    async fn start_benchmark(
         &self,
         request: Request<BenchmarkStartRequest>,
     ) -> Result<Response<BenchmarkStartedResponse>, Status> {
@@ -41,25 +50,67 @@ impl BenchmarkService for BenchmarkGrpcService {
 
         let mut state_data = self.state.state_data.write().await;
 
+        if !state_data.is_leader {
+            return Err(Status::unavailable(
+                "This operator replica is not the leader.",
+            ));
+        }
+
         state_data.last_event_time = Utc::now();
 
-        // Set Running state.
-        Benchmark::set_benchmark_state(
+        let benchmark =
+            Api::<Benchmark>::namespaced(self.k8s_client.clone(), &state_data.namespace)
+                .get_status(&state_data.benchmark_name)
+                .await
+                .map_err(|e| Status::new(Code::FailedPrecondition, e.to_string()))?;
+
+        // The run of the Benchmark links the results to the logs of its workloads.
+        let run_id = benchmark
+            .status
+            .as_ref()
+            .and_then(|status| status.run_id.clone())
+            .unwrap_or_default();
+
+        // Set Running state, only a Benchmark whose workloads were started can start benchmarking.
+        let is_started = Benchmark::transition_state(
             self.k8s_client.clone(),
             &state_data.benchmark_name,
             &state_data.namespace,
+            BenchmarkState::Pending,
             BenchmarkState::Running,
         )
         .await
         .map_err(|e| Status::new(Code::FailedPrecondition, e.to_string()))?;
 
+        if !is_started {
+            return Err(Status::failed_precondition(format!(
+                "Benchmark {} is not Pending, it can't start benchmarking.",
+                state_data.benchmark_name
+            )));
+        }
+
+        benchmark
+            .publish_event(
+                self.k8s_client.clone(),
+                EventType::Normal,
+                "Running",
+                "The Benchmark reported that it started benchmarking.",
+                None,
+            )
+            .await;
+
+        // Capture the environment the workloads run in, in the background so the workloads are not delayed.
+        let k8s_client = self.k8s_client.clone();
+        tokio::spawn(async move { benchmark.save_environment(k8s_client).await });
+
         Ok(Response::new(BenchmarkStartedResponse {
             database_connection_string: env::var("DATABASE_URL")
                 .expect("DATABASE_URL environment variable was not set."),
+            run_id,
         }))
     }
 
-    async fn benchmark_done(
+    async fn finish_benchmark(
         &self,
         request: Request<BenchmarkDoneRequest>,
     ) -> Result<Response<BenchmarkDoneResponse>, Status> {
@@ -67,22 +118,77 @@ impl BenchmarkService for BenchmarkGrpcService {
 
         let mut state_data = self.state.state_data.write().await;
 
+        if !state_data.is_leader {
+            return Err(Status::unavailable(
+                "This operator replica is not the leader.",
+            ));
+        }
+
         state_data.last_event_time = Utc::now();
 
-        // Set Done state.
-        Benchmark::set_benchmark_state(
+        // Set Done state, a Benchmark that timed out, failed or was cancelled in the meantime keeps its state.
+        let is_done = Benchmark::transition_state(
             self.k8s_client.clone(),
             &state_data.benchmark_name,
             &state_data.namespace,
+            BenchmarkState::Running,
             BenchmarkState::Done,
         )
         .await
         .map_err(|e| Status::new(Code::FailedPrecondition, e.to_string()))?;
 
+        if !is_done {
+            return Err(Status::failed_precondition(format!(
+                "Benchmark {} is not Running, it can't be done.",
+                state_data.benchmark_name
+            )));
+        }
+
+        if let Ok(benchmark) =
+            Api::<Benchmark>::namespaced(self.k8s_client.clone(), &state_data.namespace)
+                .get(&state_data.benchmark_name)
+                .await
+        {
+            benchmark
+                .publish_event(
+                    self.k8s_client.clone(),
+                    EventType::Normal,
+                    "Done",
+                    "The Benchmark reported that it finished benchmarking.",
+                    None,
+                )
+                .await;
+        }
+
         Ok(Response::new(BenchmarkDoneResponse { acknowledge: true }))
     }
 }
 
+#[tonic::async_trait]
+impl BenchmarkService for BenchmarkGrpcService {
+    async fn benchmark_started(
+        &self,
+        request: Request<BenchmarkStartRequest>,
+    ) -> Result<Response<BenchmarkStartedResponse>, Status> {
+        let start = Instant::now();
+        let result = self.start_benchmark(request).await;
+        self.observe_call("BenchmarkStarted", &result, start);
+
+        result
+    }
+
+    async fn benchmark_done(
+        &self,
+        request: Request<BenchmarkDoneRequest>,
+    ) -> Result<Response<BenchmarkDoneResponse>, Status> {
+        let start = Instant::now();
+        let result = self.finish_benchmark(request).await;
+        self.observe_call("BenchmarkDone", &result, start);
+
+        result
+    }
+}
+
 pub async fn spawn(state: &State) {
     let addr = env::var("GRPC_SOCKET_ADDRESS")
         .expect("GRPC_SOCKET_ADDRESS environment variable was not set.")
@@ -0,0 +1,241 @@
+use crate::benchmark_controller::HelmSpec;
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{error::ErrorResponse, Api, Client, Error};
+use serde_json::Value;
+use std::process::{Output, Stdio};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+/// Installs the Helm chart of `helm_spec` as `release_name` in `namespace`.
+/// The values from the ConfigMap (in `values_namespace`) are merged with the inline values, the inline values take precedence.
+pub async fn install(
+    k8s_client: Client,
+    namespace: &str,
+    values_namespace: &str,
+    release_name: &str,
+    helm_spec: &HelmSpec,
+) -> Result<(), Error> {
+    let values = resolve_values(k8s_client, values_namespace, helm_spec).await?;
+
+    let mut args = vec![
+        "install".to_string(),
+        release_name.into(),
+        helm_spec.chart_reference.clone(),
+        "-n".into(),
+        namespace.into(),
+        "--repo".into(),
+        helm_spec.repository_url.clone(),
+        // The values are passed through stdin.
+        "--values".into(),
+        "-".into(),
+    ];
+
+    if let Some(version) = &helm_spec.version {
+        args.extend(["--version".into(), version.clone()]);
+    }
+
+    // `helm install --wait` would block the reconciler, `wait` is checked with the readiness of the workload instead.
+
+    let values = serde_yaml::to_string(&values)
+        .map_err(|err| helm_error("Helm values could not be serialized.", &format!("{err}")))?;
+
+    let output = run(&args, Some(values)).await?;
+
+    if !output.status.success() {
+        return Err(helm_error(
+            "Helm install failed.",
+            String::from_utf8_lossy(&output.stderr).trim(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether a Helm release named `release_name` is installed in `namespace`.
+pub async fn release_exists(namespace: &str, release_name: &str) -> Result<bool, Error> {
+    let output = run(
+        &[
+            "status".into(),
+            release_name.into(),
+            "-n".into(),
+            namespace.into(),
+        ],
+        None,
+    )
+    .await?;
+
+    if output.status.success() {
+        return Ok(true);
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if stderr.contains("not found") {
+        Ok(false)
+    } else {
+        Err(helm_error("Helm status failed.", stderr.trim()))
+    }
+}
+
+/// Uninstalls the provided Helm releases from `namespace`, releases that are already gone are ignored.
+pub async fn uninstall(namespace: &str, release_names: &[String]) -> Result<(), Error> {
+    for release_name in release_names {
+        let output = run(
+            &[
+                "uninstall".into(),
+                release_name.clone(),
+                "-n".into(),
+                namespace.into(),
+            ],
+            None,
+        )
+        .await?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        if !output.status.success() && !stderr.contains("not found") {
+            return Err(helm_error("Helm uninstall failed.", stderr.trim()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects the values for the Helm chart, inline values are merged on top of the values from the ConfigMap.
+async fn resolve_values(
+    k8s_client: Client,
+    namespace: &str,
+    helm_spec: &HelmSpec,
+) -> Result<Value, Error> {
+    let mut values = Value::Object(Default::default());
+
+    if let Some(values_from) = &helm_spec.values_from {
+        let config_map_name = values_from.name.clone().unwrap_or_default();
+        let is_optional = values_from.optional.unwrap_or_default();
+
+        let config_map_api = Api::<ConfigMap>::namespaced(k8s_client, namespace);
+
+        let config_map_values = config_map_api
+            .get_opt(&config_map_name)
+            .await?
+            .and_then(|config_map| config_map.data)
+            .and_then(|mut data| data.remove(&values_from.key));
+
+        match config_map_values {
+            Some(config_map_values) => {
+                values = serde_yaml::from_str(&config_map_values).map_err(|err| {
+                    helm_error(
+                        "Helm values from the ConfigMap are invalid.",
+                        &format!("{err}"),
+                    )
+                })?;
+            }
+            None if is_optional => {}
+            None => {
+                return Err(helm_error(
+                    "Helm values not found.",
+                    &format!(
+                        "ConfigMap {} has no key {}.",
+                        config_map_name, values_from.key
+                    ),
+                ))
+            }
+        }
+    }
+
+    if let Some(inline_values) = &helm_spec.values {
+        merge_values(&mut values, inline_values);
+    }
+
+    Ok(values)
+}
+
+/// Deep merges `overrides` into `values`, like Helm merges multiple values files.
+fn merge_values(values: &mut Value, overrides: &Value) {
+    match (values, overrides) {
+        (Value::Object(values), Value::Object(overrides)) => {
+            for (key, override_value) in overrides {
+                match values.get_mut(key) {
+                    Some(value) => merge_values(value, override_value),
+                    None => {
+                        values.insert(key.clone(), override_value.clone());
+                    }
+                }
+            }
+        }
+        (values, overrides) => *values = overrides.clone(),
+    }
+}
+
+/// Runs the Helm CLI, `stdin` is written to the standard input of the command.
+async fn run(args: &[String], stdin: Option<String>) -> Result<Output, Error> {
+    let mut child = Command::new("helm")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| helm_error("Helm command failed.", &format!("{err}")))?;
+
+    // Dropping the handle closes stdin, so Helm stops reading.
+    if let Some(mut child_stdin) = child.stdin.take() {
+        if let Some(stdin) = stdin {
+            child_stdin
+                .write_all(stdin.as_bytes())
+                .await
+                .map_err(|err| helm_error("Helm command failed.", &format!("{err}")))?;
+        }
+    }
+
+    child
+        .wait_with_output()
+        .await
+        .map_err(|err| helm_error("Helm command failed.", &format!("{err}")))
+}
+
+fn helm_error(message: &str, reason: &str) -> Error {
+    Error::Api(ErrorResponse {
+        status: "Error".into(),
+        message: message.into(),
+        reason: reason.into(),
+        code: 500,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_values_merges_nested_objects() {
+        let mut values = json!({ "server": { "replicas": 1, "image": "nginx" }, "debug": false });
+
+        merge_values(&mut values, &json!({ "server": { "replicas": 3 } }));
+
+        assert_eq!(
+            values,
+            json!({ "server": { "replicas": 3, "image": "nginx" }, "debug": false })
+        );
+    }
+
+    #[test]
+    fn merge_values_adds_missing_keys() {
+        let mut values = json!({ "server": { "replicas": 1 } });
+
+        merge_values(&mut values, &json!({ "client": { "connections": 10 } }));
+
+        assert_eq!(
+            values,
+            json!({ "server": { "replicas": 1 }, "client": { "connections": 10 } })
+        );
+    }
+
+    #[test]
+    fn merge_values_replaces_arrays_and_scalars() {
+        let mut values = json!({ "args": ["-a", "-b"], "server": { "replicas": 1 } });
+
+        merge_values(&mut values, &json!({ "args": ["-c"], "server": null }));
+
+        assert_eq!(values, json!({ "args": ["-c"], "server": null }));
+    }
+}
@@ -0,0 +1,154 @@
+use crate::benchmark_controller::{Benchmark, BENCHMARK_UID_LABEL};
+use k8s_openapi::{
+    api::{
+        core::v1::{Namespace, ResourceQuota},
+        networking::v1::NetworkPolicy,
+    },
+    apimachinery::pkg::apis::meta::v1::OwnerReference,
+};
+use kube::{
+    api::{DeleteParams, PostParams},
+    core::ObjectMeta,
+    Api, Client, Error, Resource, ResourceExt,
+};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Running the workloads of a Benchmark in an ephemeral namespace of their own.
+impl Benchmark {
+    /// Whether the workloads of this Benchmark run in a namespace of their own.
+    pub fn is_isolated(&self) -> bool {
+        self.spec.isolation.is_some()
+    }
+
+    /// Namespace the workloads of this Benchmark are deployed in, `benchmark-<uid>` when the Benchmark is isolated.
+    pub fn workload_namespace(&self) -> String {
+        if self.is_isolated() {
+            format!("benchmark-{}", self.uid().unwrap_or_default())
+        } else {
+            self.namespace().unwrap_or("default".to_string())
+        }
+    }
+
+    /// Owner references of the workload objects.
+    /// Objects in the namespace of an isolated Benchmark can't be owned by the Benchmark, because owners need to live in the same namespace.
+    /// These objects are removed together with the namespace instead.
+    pub fn workload_owner_references(&self) -> Option<Vec<OwnerReference>> {
+        if self.is_isolated() {
+            None
+        } else {
+            Some(vec![self.controller_owner_ref(&()).unwrap()])
+        }
+    }
+
+    /// Creates the namespace of this isolated Benchmark with the NetworkPolicies and the ResourceQuota of the spec.
+    /// Objects that already exist are kept, so the namespace can be created again after a restart of the operator.
+    pub async fn create_workload_namespace(&self, k8s_client: Client) -> Result<(), Error> {
+        let Some(isolation) = &self.spec.isolation else {
+            return Ok(());
+        };
+
+        let namespace = self.workload_namespace();
+
+        info!(
+            "Creating namespace {} for Benchmark {}.",
+            namespace,
+            self.name_any()
+        );
+
+        let labels = BTreeMap::from([(
+            BENCHMARK_UID_LABEL.to_string(),
+            self.uid().unwrap_or_default(),
+        )]);
+
+        ignore_conflict(
+            Api::<Namespace>::all(k8s_client.clone())
+                .create(
+                    &PostParams::default(),
+                    &Namespace {
+                        metadata: ObjectMeta {
+                            name: Some(namespace.clone()),
+                            labels: Some(labels.clone()),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                )
+                .await,
+        )?;
+
+        let network_policy_api = Api::<NetworkPolicy>::namespaced(k8s_client.clone(), &namespace);
+
+        for (index, network_policy_spec) in isolation.network_policies.iter().enumerate() {
+            ignore_conflict(
+                network_policy_api
+                    .create(
+                        &PostParams::default(),
+                        &NetworkPolicy {
+                            metadata: ObjectMeta {
+                                name: Some(format!("benchmark-{index}")),
+                                labels: Some(labels.clone()),
+                                ..Default::default()
+                            },
+                            spec: Some(network_policy_spec.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await,
+            )?;
+        }
+
+        if let Some(resource_quota_spec) = &isolation.resource_quota {
+            ignore_conflict(
+                Api::<ResourceQuota>::namespaced(k8s_client, &namespace)
+                    .create(
+                        &PostParams::default(),
+                        &ResourceQuota {
+                            metadata: ObjectMeta {
+                                name: Some("benchmark".into()),
+                                labels: Some(labels),
+                                ..Default::default()
+                            },
+                            spec: Some(resource_quota_spec.clone()),
+                            ..Default::default()
+                        },
+                    )
+                    .await,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the namespace of this isolated Benchmark, together with everything that runs in it.
+    pub async fn remove_workload_namespace(&self, k8s_client: Client) -> Result<(), Error> {
+        if !self.is_isolated() {
+            return Ok(());
+        }
+
+        let namespace = self.workload_namespace();
+
+        info!(
+            "Removing namespace {} of Benchmark {}.",
+            namespace,
+            self.name_any()
+        );
+
+        match Api::<Namespace>::all(k8s_client)
+            .delete(&namespace, &DeleteParams::background())
+            .await
+        {
+            // Already removed (or never created).
+            Err(Error::Api(err)) if err.code == 404 => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+}
+
+/// Treats an object that already exists as created.
+fn ignore_conflict<T>(result: Result<T, Error>) -> Result<(), Error> {
+    match result {
+        Err(Error::Api(err)) if err.code == 409 => Ok(()),
+        result => result.map(|_| ()),
+    }
+}
@@ -0,0 +1,245 @@
+use crate::state::{operator_namespace, State};
+use chrono::{DateTime, Utc};
+use k8s_openapi::{
+    api::coordination::v1::{Lease, LeaseSpec},
+    apimachinery::pkg::apis::meta::v1::MicroTime,
+};
+use kube::{api::PostParams, core::ObjectMeta, Api, Client, Error};
+use std::{
+    env,
+    process::exit,
+    time::{Duration, Instant},
+};
+use tokio::{signal, time::sleep};
+use tracing::{error, info, warn};
+
+/// Time after which a follower may take over the Lease when the leader did not renew it.
+const LEASE_DURATION_SECONDS: i32 = 15;
+
+/// Time the leader keeps trying to renew the Lease before it gives up its leadership.
+const RENEW_DEADLINE_SECONDS: i64 = 10;
+
+/// Interval between the tries to acquire or renew the Lease.
+const RETRY_PERIOD: Duration = Duration::from_secs(2);
+
+/// Lease based leader election, so only one operator replica reconciles Benchmarks at a time.
+/// Adapted from the client-go leader election: <https://pkg.go.dev/k8s.io/client-go/tools/leaderelection>.
+pub struct LeaderElector {
+    lease_api: Api<Lease>,
+    lease_name: String,
+    identity: String,
+    observed_lease: Option<ObservedLease>, // Last observed version of the Lease, to detect an expired Lease without comparing clocks.
+}
+
+/// A version of the Lease and the local time it was first observed.
+struct ObservedLease {
+    resource_version: Option<String>, // Resource version of the Lease, it changes whenever the holder renews the Lease.
+    observed_at: Instant,             // Local time at which this version was first observed.
+}
+
+impl LeaderElector {
+    pub fn new(k8s_client: Client) -> LeaderElector {
+        // The name of the Pod is provided by the downward API when running in the cluster.
+        let identity = env::var("POD_NAME")
+            .or_else(|_| env::var("HOSTNAME"))
+            .unwrap_or("benchmark-operator".to_string());
+
+        LeaderElector {
+            lease_api: Api::<Lease>::namespaced(k8s_client, &operator_namespace()),
+            lease_name: env::var("LEADER_ELECTION_LEASE_NAME")
+                .unwrap_or("benchmark-operator-leader".to_string()),
+            identity,
+            observed_lease: None,
+        }
+    }
+
+    /// Records the observed `resource_version` of the Lease, returns the local time at which it was first observed.
+    /// The time is only reset when the Lease changed, so it tells how long the Lease has not been renewed.
+    fn observe(&mut self, resource_version: Option<String>, now: Instant) -> Instant {
+        match &self.observed_lease {
+            Some(observed_lease) if observed_lease.resource_version == resource_version => {
+                observed_lease.observed_at
+            }
+            _ => {
+                self.observed_lease = Some(ObservedLease {
+                    resource_version,
+                    observed_at: now,
+                });
+                now
+            }
+        }
+    }
+
+    /// Tries to acquire the Lease or renew it when this replica already holds it.
+    /// Returns whether this replica is the leader.
+    pub async fn try_acquire_or_renew(&mut self) -> Result<bool, Error> {
+        let now = Utc::now();
+
+        let Some(mut lease) = self.lease_api.get_opt(&self.lease_name).await? else {
+            // Nobody holds the Lease yet.
+            let lease = Lease {
+                metadata: ObjectMeta {
+                    name: Some(self.lease_name.clone()),
+                    ..Default::default()
+                },
+                spec: Some(LeaseSpec {
+                    holder_identity: Some(self.identity.clone()),
+                    lease_duration_seconds: Some(LEASE_DURATION_SECONDS),
+                    acquire_time: Some(MicroTime(now)),
+                    renew_time: Some(MicroTime(now)),
+                    lease_transitions: Some(0),
+                }),
+            };
+
+            let result = self.lease_api.create(&PostParams::default(), &lease).await;
+            return self.ignore_conflict(result);
+        };
+
+        // The renew time is set with the clock of the holder, which might be skewed compared to the local clock.
+        // Like client-go, the Lease only expires once it stayed unchanged for the lease duration of local time.
+        let observed_at = self.observe(lease.metadata.resource_version.clone(), Instant::now());
+
+        let spec = lease.spec.get_or_insert_with(Default::default);
+
+        let is_holder = spec.holder_identity.as_deref() == Some(self.identity.as_str());
+        let lease_duration = Duration::from_secs(
+            spec.lease_duration_seconds
+                .unwrap_or(LEASE_DURATION_SECONDS)
+                .max(0) as u64,
+        );
+        let is_expired = spec.holder_identity.is_none()
+            || spec.renew_time.is_none()
+            || observed_at.elapsed() > lease_duration;
+
+        if !is_holder && !is_expired {
+            return Ok(false);
+        }
+
+        if !is_holder {
+            // Take over the expired Lease.
+            spec.holder_identity = Some(self.identity.clone());
+            spec.acquire_time = Some(MicroTime(now));
+            spec.lease_transitions = Some(spec.lease_transitions.unwrap_or_default() + 1);
+        }
+
+        spec.lease_duration_seconds = Some(LEASE_DURATION_SECONDS);
+        spec.renew_time = Some(MicroTime(now));
+
+        // The replaced Lease carries the resource version that was read,
+        // so a replica that updated the Lease in the meantime causes a conflict instead of two leaders.
+        let result = self
+            .lease_api
+            .replace(&self.lease_name, &PostParams::default(), &lease)
+            .await;
+        self.ignore_conflict(result)
+    }
+
+    /// A conflict means another replica acquired or renewed the Lease first.
+    /// The Lease written by this replica is observed, so it does not count as a renewal by another replica.
+    fn ignore_conflict(&mut self, result: Result<Lease, Error>) -> Result<bool, Error> {
+        match result {
+            Ok(lease) => {
+                self.observe(lease.metadata.resource_version, Instant::now());
+                Ok(true)
+            }
+            Err(Error::Api(err)) if err.code == 409 => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Entry-point to start the leader election, keeps `is_leader` of the shared state up to date.
+/// When the leadership is lost, the operator exits so it restarts as a follower with a clean state.
+pub async fn run(state: &State) {
+    let k8s_client = Client::try_default()
+        .await
+        .expect("Leader election: Failed to create kube client.");
+
+    let mut leader_elector = LeaderElector::new(k8s_client);
+
+    info!(
+        "Leader election started for {} as {}.",
+        leader_elector.lease_name, leader_elector.identity
+    );
+
+    let mut last_renew_time: Option<DateTime<Utc>> = None;
+
+    loop {
+        match leader_elector.try_acquire_or_renew().await {
+            Ok(true) => {
+                if last_renew_time.is_none() {
+                    info!("Acquired leadership, this replica starts reconciling Benchmarks.");
+                    state.state_data.write().await.is_leader = true;
+                }
+
+                last_renew_time = Some(Utc::now());
+            }
+            Ok(false) if last_renew_time.is_some() => {
+                error!("Leadership was taken over by another replica, exiting.");
+                exit(1);
+            }
+            Ok(false) => {}
+            Err(err) => warn!("Could not acquire or renew the leader Lease: {err:?}"),
+        }
+
+        // Give up the leadership when the Lease could not be renewed in time.
+        if let Some(last_renew_time) = last_renew_time {
+            if Utc::now() - last_renew_time > chrono::Duration::seconds(RENEW_DEADLINE_SECONDS) {
+                error!("Failed to renew the leader Lease in time, exiting.");
+                exit(1);
+            }
+        }
+
+        tokio::select! {
+            _ = sleep(RETRY_PERIOD) => {},
+            _ = signal::ctrl_c() => break,
+        }
+    }
+
+    info!("Leader election received shutdown signal");
+}
+
+/// Waits until this replica is elected as the leader.
+pub async fn wait_for_leadership(state: &State) {
+    while !state.state_data.read().await.is_leader {
+        sleep(RETRY_PERIOD).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leader_elector() -> LeaderElector {
+        let k8s_client =
+            Client::try_from(kube::Config::new("http://localhost".parse().unwrap())).unwrap();
+
+        LeaderElector {
+            lease_api: Api::namespaced(k8s_client, "default"),
+            lease_name: "lease".into(),
+            identity: "replica".into(),
+            observed_lease: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn unchanged_lease_keeps_first_observation_time() {
+        let mut leader_elector = leader_elector();
+        let first = Instant::now();
+        let later = first + Duration::from_secs(20);
+
+        assert_eq!(leader_elector.observe(Some("1".into()), first), first);
+        assert_eq!(leader_elector.observe(Some("1".into()), later), first);
+    }
+
+    #[tokio::test]
+    async fn renewed_lease_resets_observation_time() {
+        let mut leader_elector = leader_elector();
+        let first = Instant::now();
+        let later = first + Duration::from_secs(20);
+
+        leader_elector.observe(Some("1".into()), first);
+
+        assert_eq!(leader_elector.observe(Some("2".into()), later), later);
+    }
+}
@@ -1,4 +1,19 @@
 pub mod benchmark_controller;
+pub mod crd_versions;
+pub mod environment;
+pub mod events;
 pub mod grpc;
+pub mod helm;
+pub mod isolation;
+pub mod leader_election;
+pub mod matrix;
+pub mod metrics;
+pub mod queue;
+pub mod readiness;
+pub mod resource_usage;
+pub mod schedule_controller;
 pub mod state;
+pub mod suite_controller;
+pub mod ttl;
 pub mod web;
+pub mod workloads;
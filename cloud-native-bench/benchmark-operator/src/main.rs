@@ -1,4 +1,7 @@
-use benchmark_operator::{benchmark_controller, grpc, state::State, web};
+use benchmark_operator::{
+    benchmark_controller, grpc, leader_election, resource_usage, schedule_controller, state::State,
+    suite_controller, web,
+};
 use dotenv::dotenv;
 
 /// Runs the operator
@@ -15,7 +18,18 @@ async fn main() {
 
     let web_server = web::spawn(&state);
     let grpc_server = grpc::spawn(&state);
-    let controller = benchmark_controller::run(&state);
+    let leader_election = leader_election::run(&state);
 
-    tokio::join!(web_server, grpc_server, controller);
+    // Only the leader replica runs the controllers.
+    let controller = async {
+        leader_election::wait_for_leadership(&state).await;
+        tokio::join!(
+            benchmark_controller::run(&state),
+            schedule_controller::run(&state),
+            suite_controller::run(&state),
+            resource_usage::run(&state)
+        );
+    };
+
+    tokio::join!(web_server, grpc_server, leader_election, controller);
 }
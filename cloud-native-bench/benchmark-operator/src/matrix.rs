@@ -0,0 +1,470 @@
+use crate::benchmark_controller::{
+    child_name, Benchmark, BenchmarkSpec, BenchmarkState, BenchmarkStatus, MatrixRun,
+    MAX_NAME_LENGTH,
+};
+use kube::{
+    api::{ListParams, Patch, PatchParams, PostParams},
+    core::ObjectMeta,
+    error::ErrorResponse,
+    runtime::{controller::Action, events::EventType},
+    Api, Client, Error, Resource, ResourceExt,
+};
+use schemars::{gen::SchemaGenerator, schema::Schema};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Label that is added to the runs of a matrix Benchmark, its value is the UID of the matrix Benchmark.
+pub const MATRIX_PARENT_LABEL: &str = "michiel.van.kenhove.ugent.be/matrix-parent";
+
+/// Annotation that holds the matrix parameters of a run as a JSON object.
+pub const MATRIX_PARAMETERS_ANNOTATION: &str = "michiel.van.kenhove.ugent.be/matrix-parameters";
+
+/// Maximum number of parameters of a matrix and values of a parameter, enforced by the CRD schema.
+const MAX_MATRIX_PARAMETERS: u32 = 10;
+const MAX_MATRIX_VALUES: u32 = 100;
+
+/// Maximum number of runs a matrix Benchmark is expanded into, a larger matrix fails the Benchmark.
+pub const MAX_MATRIX_COMBINATIONS: usize = 500;
+
+/// Schema of the matrix, which limits the number of parameters and the number of values per parameter.
+pub(crate) fn matrix_schema(gen: &mut SchemaGenerator) -> Schema {
+    let mut schema = gen
+        .subschema_for::<Option<BTreeMap<String, Vec<String>>>>()
+        .into_object();
+
+    let object = schema.object();
+    object.max_properties = Some(MAX_MATRIX_PARAMETERS);
+
+    if let Some(values) = object.additional_properties.as_mut() {
+        if let Schema::Object(values) = values.as_mut() {
+            values.array().max_items = Some(MAX_MATRIX_VALUES);
+        }
+    }
+
+    Schema::Object(schema)
+}
+
+/// Expanding a matrix Benchmark into its runs.
+impl Benchmark {
+    /// Whether this Benchmark is a matrix Benchmark, which is expanded into runs instead of being queued itself.
+    pub fn is_matrix(&self) -> bool {
+        self.spec.matrix.is_some()
+    }
+
+    /// Creates the missing runs of this matrix Benchmark and aggregates the states of the runs in its status.
+    /// The runs are regular Benchmarks that are owned by this Benchmark, so they are queued like any other Benchmark.
+    pub async fn reconcile_matrix(&self, k8s_client: Client) -> Result<Action, Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
+
+        let benchmark_api = Api::<Benchmark>::namespaced(k8s_client.clone(), &namespace);
+
+        if self.status.is_none() {
+            let status = json!({
+                "status": BenchmarkStatus{state: BenchmarkState::Pending, ..Default::default()}
+            });
+            benchmark_api
+                .patch_status(&name, &PatchParams::default(), &Patch::Merge(&status))
+                .await?;
+        }
+
+        let is_finished = self
+            .status
+            .as_ref()
+            .is_some_and(|status| status.state.is_finished());
+
+        // Checked before the combinations are expanded, a large matrix would be expanded into thousands of runs.
+        let is_too_large = match self.matrix_combination_count() {
+            Some(count) => count > MAX_MATRIX_COMBINATIONS,
+            None => true,
+        };
+
+        if is_too_large {
+            Benchmark::update_status(k8s_client, &name, &namespace, |status| {
+                status.finish(
+                    BenchmarkState::Failed,
+                    "MatrixTooLarge",
+                    &format!(
+                        "The matrix has more than {MAX_MATRIX_COMBINATIONS} combinations, split it into smaller matrices."
+                    ),
+                )
+            })
+            .await?;
+
+            return Ok(Action::await_change());
+        }
+
+        let combinations = self.matrix_combinations();
+
+        if combinations.is_empty() {
+            Benchmark::update_status(k8s_client, &name, &namespace, |status| {
+                status.finish(
+                    BenchmarkState::Failed,
+                    "InvalidMatrix",
+                    "The matrix has no combinations, every parameter needs at least one value.",
+                )
+            })
+            .await?;
+
+            return Ok(Action::await_change());
+        }
+
+        let runs = benchmark_api
+            .list(&ListParams::default().labels(&format!(
+                "{}={}",
+                MATRIX_PARENT_LABEL,
+                self.uid().unwrap_or_default()
+            )))
+            .await?
+            .items;
+
+        // Cancelling a matrix Benchmark cancels its runs that did not finish yet.
+        if self.spec.cancel && !is_finished {
+            for run in runs.iter().filter(|run| {
+                !run.spec.cancel
+                    && !run
+                        .status
+                        .as_ref()
+                        .is_some_and(|status| status.state.is_finished())
+            }) {
+                info!(
+                    "Cancelling run {} of matrix Benchmark {}.",
+                    run.name_any(),
+                    name
+                );
+
+                benchmark_api
+                    .patch(
+                        &run.name_any(),
+                        &PatchParams::default(),
+                        &Patch::Merge(&json!({ "spec": { "cancel": true } })),
+                    )
+                    .await?;
+            }
+
+            Benchmark::update_status(k8s_client, &name, &namespace, |status| {
+                status.finish(
+                    BenchmarkState::Cancelled,
+                    "Cancelled",
+                    "The matrix Benchmark was cancelled, its runs that did not finish are cancelled as well.",
+                )
+            })
+            .await?;
+
+            return Ok(Action::await_change());
+        }
+
+        let mut matrix_runs = Vec::new();
+
+        for (index, parameters) in combinations.into_iter().enumerate() {
+            let run_name = self.matrix_run_name(index);
+            let run = runs.iter().find(|run| run.name_any() == run_name);
+
+            // Runs are created in order, so runs with the same priority are queued in the order of the combinations.
+            if run.is_none() && !is_finished {
+                info!("Creating run {} of matrix Benchmark {}.", run_name, name);
+
+                match benchmark_api
+                    .create(
+                        &PostParams::default(),
+                        &self.matrix_run(&run_name, &parameters)?,
+                    )
+                    .await
+                {
+                    Ok(run) => {
+                        self.publish_event(
+                            k8s_client.clone(),
+                            EventType::Normal,
+                            "RunCreated",
+                            &format!("Created run {run_name} of the matrix."),
+                            Some(run.object_ref(&())),
+                        )
+                        .await
+                    }
+                    // Created in the meantime.
+                    Err(Error::Api(err)) if err.code == 409 => {}
+                    Err(err) => return Err(err),
+                }
+            }
+
+            matrix_runs.push(MatrixRun {
+                name: run_name,
+                parameters,
+                state: run
+                    .and_then(|run| run.status.as_ref())
+                    .map(|status| status.state.clone()),
+            });
+        }
+
+        let run_count = matrix_runs.len();
+        let completed_count = matrix_runs
+            .iter()
+            .filter(|matrix_run| matrix_run.state == Some(BenchmarkState::Completed))
+            .count();
+        let is_all_finished = matrix_runs.iter().all(|matrix_run| {
+            matrix_run
+                .state
+                .as_ref()
+                .is_some_and(BenchmarkState::is_finished)
+        });
+        let is_any_started = matrix_runs.iter().any(|matrix_run| {
+            matrix_run
+                .state
+                .as_ref()
+                .is_some_and(|state| *state != BenchmarkState::Pending)
+        });
+
+        Benchmark::update_status(k8s_client, &name, &namespace, |status| {
+            status.matrix_runs = matrix_runs;
+
+            if status.state.is_finished() {
+                return;
+            }
+
+            if is_all_finished && completed_count == run_count {
+                status.finish(
+                    BenchmarkState::Completed,
+                    "MatrixCompleted",
+                    &format!("All {run_count} runs completed."),
+                );
+            } else if is_all_finished {
+                status.finish(
+                    BenchmarkState::Failed,
+                    "MatrixRunsFailed",
+                    &format!(
+                        "{} of the {} runs did not complete.",
+                        run_count - completed_count,
+                        run_count
+                    ),
+                );
+            } else if is_any_started {
+                status.state = BenchmarkState::Running;
+            }
+        })
+        .await?;
+
+        Ok(Action::await_change())
+    }
+
+    /// Number of combinations of the matrix parameters, `None` when the number overflows.
+    pub fn matrix_combination_count(&self) -> Option<usize> {
+        let Some(matrix) = &self.spec.matrix else {
+            return Some(0);
+        };
+
+        matrix
+            .values()
+            .try_fold(1usize, |count, values| count.checked_mul(values.len()))
+    }
+
+    /// Every combination of the matrix parameters, the parameters are combined in the order of their keys.
+    /// Check the number of combinations with `matrix_combination_count` first, the number grows exponentially.
+    pub fn matrix_combinations(&self) -> Vec<BTreeMap<String, String>> {
+        let Some(matrix) = &self.spec.matrix else {
+            return Vec::new();
+        };
+
+        matrix
+            .iter()
+            .fold(vec![BTreeMap::new()], |combinations, (key, values)| {
+                combinations
+                    .iter()
+                    .flat_map(|combination| {
+                        values.iter().map(move |value| {
+                            let mut combination = combination.clone();
+                            combination.insert(key.clone(), value.clone());
+                            combination
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    /// Name of the run at `index`, `<benchmark name>-<index>` limited to 253 characters.
+    fn matrix_run_name(&self, index: usize) -> String {
+        child_name(&self.name_any(), &format!("-{index}"), MAX_NAME_LENGTH)
+    }
+
+    /// Matrix parameters of this run, empty when this Benchmark is not a run of a matrix Benchmark.
+    pub fn matrix_parameters(&self) -> BTreeMap<String, String> {
+        self.annotations()
+            .get(MATRIX_PARAMETERS_ANNOTATION)
+            .and_then(|parameters| serde_json::from_str(parameters).ok())
+            .unwrap_or_default()
+    }
+
+    /// Child Benchmark that runs this Benchmark with the `parameters` substituted in its spec.
+    fn matrix_run(
+        &self,
+        run_name: &str,
+        parameters: &BTreeMap<String, String>,
+    ) -> Result<Benchmark, Error> {
+        let mut spec = serde_json::to_value(BenchmarkSpec {
+            matrix: None,
+            ..self.spec.clone()
+        })
+        .map_err(Error::SerdeError)?;
+
+        substitute_parameters(&mut spec, parameters);
+
+        let mut spec: BenchmarkSpec = serde_json::from_value(spec).map_err(Error::SerdeError)?;
+
+        // Tell the runs apart by their parameters.
+        spec.title = format!(
+            "{} ({})",
+            spec.title,
+            parameters
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+
+        let mut run = Benchmark::new(run_name, spec);
+
+        // The runs of a matrix Benchmark of a suite are part of the suite run as well.
+        let (mut labels, mut annotations) = self.suite_metadata();
+        labels.insert(MATRIX_PARENT_LABEL.into(), self.uid().unwrap_or_default());
+        annotations.insert(
+            MATRIX_PARAMETERS_ANNOTATION.into(),
+            serde_json::to_string(parameters).map_err(Error::SerdeError)?,
+        );
+
+        run.metadata = ObjectMeta {
+            name: Some(run_name.into()),
+            namespace: self.namespace(),
+            labels: Some(labels),
+            annotations: Some(annotations),
+            owner_references: Some(vec![self.controller_owner_ref(&()).ok_or_else(|| {
+                Error::Api(ErrorResponse {
+                    status: "Error".into(),
+                    message: "Matrix run could not be created.".into(),
+                    reason: "Benchmark has no UID".into(),
+                    code: 400,
+                })
+            })?]),
+            ..Default::default()
+        };
+
+        Ok(run)
+    }
+}
+
+/// Replaces `{{ matrix.<key> }}` (and `{{matrix.<key>}}`) in every string of `value` by the value of the parameter.
+fn substitute_parameters(value: &mut Value, parameters: &BTreeMap<String, String>) {
+    match value {
+        Value::String(string) => {
+            for (key, parameter) in parameters {
+                *string = string
+                    .replace(&format!("{{{{ matrix.{key} }}}}"), parameter)
+                    .replace(&format!("{{{{matrix.{key}}}}}"), parameter);
+            }
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| substitute_parameters(value, parameters)),
+        Value::Object(values) => values
+            .values_mut()
+            .for_each(|value| substitute_parameters(value, parameters)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix_benchmark(matrix: &[(&str, &[&str])]) -> Benchmark {
+        Benchmark::new(
+            "benchmark",
+            BenchmarkSpec {
+                matrix: Some(
+                    matrix
+                        .iter()
+                        .map(|(key, values)| {
+                            (
+                                key.to_string(),
+                                values.iter().map(|value| value.to_string()).collect(),
+                            )
+                        })
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn parameters(parameters: &[(&str, &str)]) -> BTreeMap<String, String> {
+        parameters
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn matrix_combinations_are_ordered_by_key() {
+        let benchmark = matrix_benchmark(&[("size", &["1", "2"]), ("connections", &["10", "20"])]);
+
+        assert_eq!(benchmark.matrix_combination_count(), Some(4));
+        assert_eq!(
+            benchmark.matrix_combinations(),
+            vec![
+                parameters(&[("connections", "10"), ("size", "1")]),
+                parameters(&[("connections", "10"), ("size", "2")]),
+                parameters(&[("connections", "20"), ("size", "1")]),
+                parameters(&[("connections", "20"), ("size", "2")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn matrix_without_values_has_no_combinations() {
+        let benchmark = matrix_benchmark(&[("size", &["1", "2"]), ("connections", &[])]);
+
+        assert_eq!(benchmark.matrix_combination_count(), Some(0));
+        assert!(benchmark.matrix_combinations().is_empty());
+    }
+
+    #[test]
+    fn matrix_combination_count_does_not_overflow() {
+        // 100^20 combinations.
+        let matrix = (0..20)
+            .map(|index| (format!("parameter-{index}"), vec!["value".to_string(); 100]))
+            .collect();
+        let benchmark = Benchmark::new(
+            "benchmark",
+            BenchmarkSpec {
+                matrix: Some(matrix),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(benchmark.matrix_combination_count(), None);
+    }
+
+    #[test]
+    fn substitute_parameters_replaces_every_string() {
+        let mut value = json!({
+            "title": "Size {{ matrix.size }}",
+            "args": ["--size={{matrix.size}}", "--connections={{ matrix.connections }}"],
+            "replicas": 1,
+            "unknown": "{{ matrix.unknown }}"
+        });
+
+        substitute_parameters(
+            &mut value,
+            &parameters(&[("size", "1024"), ("connections", "10")]),
+        );
+
+        assert_eq!(
+            value,
+            json!({
+                "title": "Size 1024",
+                "args": ["--size=1024", "--connections=10"],
+                "replicas": 1,
+                "unknown": "{{ matrix.unknown }}"
+            })
+        );
+    }
+}
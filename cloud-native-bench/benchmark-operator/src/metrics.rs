@@ -0,0 +1,435 @@
+use crate::{benchmark_controller::Benchmark, state::State};
+use kube::ResourceExt;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Upper bounds (in seconds) of the buckets of the gRPC call latency histogram.
+const GRPC_DURATION_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Upper bounds (in seconds) of the buckets of the Benchmark duration histogram.
+const BENCHMARK_DURATION_BUCKETS: [f64; 10] = [
+    10.0, 30.0, 60.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0, 21600.0, 86400.0,
+];
+
+/// Prometheus histogram with cumulative buckets.
+#[derive(Debug, Clone)]
+struct Histogram {
+    bounds: &'static [f64],
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Histogram {
+        Histogram {
+            bounds,
+            bucket_counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bound, bucket_count) in self.bounds.iter().zip(self.bucket_counts.iter_mut()) {
+            if value <= *bound {
+                *bucket_count += 1;
+            }
+        }
+
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Counters and histograms that are updated by the controllers and the gRPC server.
+#[derive(Debug, Default)]
+struct MetricsData {
+    reconciliations: BTreeMap<String, u64>,  // Per controller.
+    reconcile_errors: BTreeMap<String, u64>, // Per controller.
+    benchmark_durations: BTreeMap<String, Histogram>, // Per final state.
+    grpc_calls: BTreeMap<(String, String), u64>, // Per method and status code.
+    grpc_durations: BTreeMap<String, Histogram>, // Per method.
+}
+
+/// Metrics of the operator, exposed in the Prometheus text format on `/metrics`.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    data: Mutex<MetricsData>,
+}
+
+impl Metrics {
+    /// Counts a reconciliation of the `controller` (e.g., `benchmark`).
+    pub fn count_reconciliation(&self, controller: &str) {
+        *self
+            .data
+            .lock()
+            .unwrap()
+            .reconciliations
+            .entry(controller.into())
+            .or_default() += 1;
+    }
+
+    /// Counts a failed reconciliation of the `controller`.
+    pub fn count_reconcile_error(&self, controller: &str) {
+        *self
+            .data
+            .lock()
+            .unwrap()
+            .reconcile_errors
+            .entry(controller.into())
+            .or_default() += 1;
+    }
+
+    /// Records the time between the start of the workloads of a Benchmark and the moment it reached its final `state`.
+    pub fn observe_benchmark_duration(&self, state: &str, duration: Duration) {
+        self.data
+            .lock()
+            .unwrap()
+            .benchmark_durations
+            .entry(state.into())
+            .or_insert_with(|| Histogram::new(&BENCHMARK_DURATION_BUCKETS))
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Records a gRPC call of `method` that ended with the status `code` (e.g., `Ok`).
+    pub fn observe_grpc_call(&self, method: &str, code: &str, duration: Duration) {
+        let mut data = self.data.lock().unwrap();
+
+        *data
+            .grpc_calls
+            .entry((method.into(), code.into()))
+            .or_default() += 1;
+
+        data.grpc_durations
+            .entry(method.into())
+            .or_insert_with(|| Histogram::new(&GRPC_DURATION_BUCKETS))
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Renders the counters and histograms in the Prometheus text format.
+    fn render(&self, out: &mut String) {
+        let data = self.data.lock().unwrap();
+
+        write_header(
+            out,
+            "benchmark_operator_reconciliations_total",
+            "Number of reconciliations per controller.",
+            "counter",
+        );
+        for (controller, count) in data.reconciliations.iter() {
+            write_sample(
+                out,
+                "benchmark_operator_reconciliations_total",
+                &[("controller", controller)],
+                *count as f64,
+            );
+        }
+
+        write_header(
+            out,
+            "benchmark_operator_reconcile_errors_total",
+            "Number of failed reconciliations per controller.",
+            "counter",
+        );
+        for (controller, count) in data.reconcile_errors.iter() {
+            write_sample(
+                out,
+                "benchmark_operator_reconcile_errors_total",
+                &[("controller", controller)],
+                *count as f64,
+            );
+        }
+
+        write_header(
+            out,
+            "benchmark_operator_benchmark_duration_seconds",
+            "Time between the start of the workloads of a Benchmark and its final state.",
+            "histogram",
+        );
+        for (state, histogram) in data.benchmark_durations.iter() {
+            write_histogram(
+                out,
+                "benchmark_operator_benchmark_duration_seconds",
+                ("state", state),
+                histogram,
+            );
+        }
+
+        write_header(
+            out,
+            "benchmark_operator_grpc_calls_total",
+            "Number of gRPC calls per method and status code.",
+            "counter",
+        );
+        for ((method, code), count) in data.grpc_calls.iter() {
+            write_sample(
+                out,
+                "benchmark_operator_grpc_calls_total",
+                &[("method", method), ("code", code)],
+                *count as f64,
+            );
+        }
+
+        write_header(
+            out,
+            "benchmark_operator_grpc_call_duration_seconds",
+            "Latency of the gRPC calls per method.",
+            "histogram",
+        );
+        for (method, histogram) in data.grpc_durations.iter() {
+            write_histogram(
+                out,
+                "benchmark_operator_grpc_call_duration_seconds",
+                ("method", method),
+                histogram,
+            );
+        }
+    }
+}
+
+/// Renders all the metrics of the operator, the queue and the Benchmark states are read from the cache of the Benchmark controller.
+/// Replicas that don't run the controller (i.e., followers) only render the metrics of this process.
+pub async fn render(state: &State) -> String {
+    let mut out = String::new();
+
+    let state_data = state.state_data.read().await.clone();
+
+    write_header(
+        &mut out,
+        "benchmark_operator_is_leader",
+        "Whether this operator replica is the leader, only the leader reconciles Benchmarks.",
+        "gauge",
+    );
+    write_sample(
+        &mut out,
+        "benchmark_operator_is_leader",
+        &[],
+        if state_data.is_leader { 1.0 } else { 0.0 },
+    );
+
+    write_header(
+        &mut out,
+        "benchmark_operator_last_event_timestamp_seconds",
+        "Time of the last event of the active Benchmark (e.g., its start or a gRPC call), a stalled pipeline stops updating it.",
+        "gauge",
+    );
+    write_sample(
+        &mut out,
+        "benchmark_operator_last_event_timestamp_seconds",
+        &[],
+        state_data.last_event_time.timestamp_millis() as f64 / 1000.0,
+    );
+
+    if let Some(benchmark_store) = state.benchmark_store.get() {
+        render_benchmarks(&mut out, &benchmark_store.state());
+    }
+
+    state.metrics.render(&mut out);
+
+    out
+}
+
+/// Renders the queue length and the state of every Benchmark.
+fn render_benchmarks(out: &mut String, benchmarks: &[Arc<Benchmark>]) {
+    // The same Benchmarks as the cluster-wide queue holds, see `BenchmarkQueue::init`.
+    let queue_length = benchmarks
+        .iter()
+        .filter(|benchmark| {
+            !benchmark.is_matrix()
+                && benchmark
+                    .status
+                    .as_ref()
+                    .is_some_and(|status| !status.state.is_finished())
+        })
+        .count();
+
+    write_header(
+        out,
+        "benchmark_operator_queue_length",
+        "Number of Benchmarks in the cluster-wide queue, including the active Benchmark.",
+        "gauge",
+    );
+    write_sample(
+        out,
+        "benchmark_operator_queue_length",
+        &[],
+        queue_length as f64,
+    );
+
+    let mut state_counts: BTreeMap<String, u64> = BTreeMap::new();
+
+    write_header(
+        out,
+        "benchmark_operator_benchmark_state",
+        "State of each Benchmark, the sample of the current state is 1.",
+        "gauge",
+    );
+    for benchmark in benchmarks.iter() {
+        let benchmark_state = benchmark
+            .status
+            .as_ref()
+            .map(|status| format!("{:?}", status.state))
+            .unwrap_or("Unknown".to_string());
+
+        write_sample(
+            out,
+            "benchmark_operator_benchmark_state",
+            &[
+                ("namespace", &benchmark.namespace().unwrap_or_default()),
+                ("name", &benchmark.name_any()),
+                ("state", &benchmark_state),
+            ],
+            1.0,
+        );
+
+        *state_counts.entry(benchmark_state).or_default() += 1;
+    }
+
+    write_header(
+        out,
+        "benchmark_operator_benchmarks",
+        "Number of Benchmarks per state.",
+        "gauge",
+    );
+    for (benchmark_state, count) in state_counts.iter() {
+        write_sample(
+            out,
+            "benchmark_operator_benchmarks",
+            &[("state", benchmark_state)],
+            *count as f64,
+        );
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, metric_type: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {metric_type}");
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    let labels: Vec<String> = labels
+        .iter()
+        .map(|(label, label_value)| format!("{}=\"{}\"", label, escape(label_value)))
+        .collect();
+
+    if labels.is_empty() {
+        let _ = writeln!(out, "{name} {value}");
+    } else {
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels.join(","), value);
+    }
+}
+
+fn write_histogram(out: &mut String, name: &str, label: (&str, &str), histogram: &Histogram) {
+    for (bound, bucket_count) in histogram.bounds.iter().zip(histogram.bucket_counts.iter()) {
+        write_sample(
+            out,
+            &format!("{name}_bucket"),
+            &[label, ("le", &bound.to_string())],
+            *bucket_count as f64,
+        );
+    }
+
+    write_sample(
+        out,
+        &format!("{name}_bucket"),
+        &[label, ("le", "+Inf")],
+        histogram.count as f64,
+    );
+    write_sample(out, &format!("{name}_sum"), &[label], histogram.sum);
+    write_sample(
+        out,
+        &format!("{name}_count"),
+        &[label],
+        histogram.count as f64,
+    );
+}
+
+/// Escapes a label value, see <https://prometheus.io/docs/instrumenting/exposition_formats/#text-format-details>.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let mut histogram = Histogram::new(&[1.0, 5.0, 10.0]);
+
+        for value in [0.5, 1.0, 3.0, 20.0] {
+            histogram.observe(value);
+        }
+
+        assert_eq!(histogram.bucket_counts, vec![2, 3, 3]);
+        assert_eq!(histogram.count, 4);
+        assert_eq!(histogram.sum, 24.5);
+    }
+
+    #[test]
+    fn histogram_is_rendered_with_inf_bucket() {
+        let mut histogram = Histogram::new(&[1.0, 2.5]);
+        histogram.observe(2.0);
+        histogram.observe(3.0);
+
+        let mut out = String::new();
+        write_histogram(&mut out, "latency_seconds", ("method", "Start"), &histogram);
+
+        assert_eq!(
+            out,
+            "latency_seconds_bucket{method=\"Start\",le=\"1\"} 0\n\
+             latency_seconds_bucket{method=\"Start\",le=\"2.5\"} 1\n\
+             latency_seconds_bucket{method=\"Start\",le=\"+Inf\"} 2\n\
+             latency_seconds_sum{method=\"Start\"} 5\n\
+             latency_seconds_count{method=\"Start\"} 2\n"
+        );
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(escape(r#"a "quoted" \ value"#), r#"a \"quoted\" \\ value"#);
+        assert_eq!(escape("two\nlines"), "two\\nlines");
+
+        let mut out = String::new();
+        write_sample(&mut out, "benchmarks", &[("name", "a\"b")], 1.0);
+
+        assert_eq!(out, "benchmarks{name=\"a\\\"b\"} 1\n");
+    }
+
+    #[test]
+    fn samples_without_labels_have_no_braces() {
+        let mut out = String::new();
+        write_sample(&mut out, "benchmark_operator_is_leader", &[], 1.0);
+
+        assert_eq!(out, "benchmark_operator_is_leader 1\n");
+    }
+
+    #[test]
+    fn counters_are_rendered_per_label() {
+        let metrics = Metrics::default();
+        metrics.count_reconciliation("benchmark");
+        metrics.count_reconciliation("benchmark");
+        metrics.count_reconciliation("benchmark_suite");
+
+        let mut out = String::new();
+        metrics.render(&mut out);
+
+        assert!(
+            out.contains("benchmark_operator_reconciliations_total{controller=\"benchmark\"} 2\n")
+        );
+        assert!(out.contains(
+            "benchmark_operator_reconciliations_total{controller=\"benchmark_suite\"} 1\n"
+        ));
+        assert!(out.contains("# TYPE benchmark_operator_reconcile_errors_total counter\n"));
+    }
+}
@@ -0,0 +1,364 @@
+use crate::{
+    benchmark_controller::{Benchmark, BenchmarkState},
+    state::operator_namespace,
+};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{Patch, PatchParams, PostParams},
+    core::ObjectMeta,
+    Api, Client, Error, ResourceExt,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, env};
+use tracing::warn;
+
+/// Key in the ConfigMap data that holds the queue.
+const QUEUE_KEY: &str = "queue";
+
+/// A Benchmark in the queue.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEntry {
+    pub namespace: String,
+    pub name: String,
+    pub priority: i32,
+    pub is_started: bool, // Started Benchmarks are first in queue and can't be moved back by a higher priority.
+}
+
+impl QueueEntry {
+    fn is_benchmark(&self, namespace: &str, name: &str) -> bool {
+        self.namespace == namespace && self.name == name
+    }
+}
+
+/// Inserts `entry` behind the started entries and the entries with the same or a higher priority, returns its position.
+/// An entry that is already queued keeps its position.
+fn insert_entry(entries: &mut Vec<QueueEntry>, entry: QueueEntry) -> u32 {
+    if let Some(position) = entries
+        .iter()
+        .position(|queued_entry| queued_entry.is_benchmark(&entry.namespace, &entry.name))
+    {
+        return position as u32;
+    }
+
+    let position = entries
+        .iter()
+        .position(|queued_entry| !queued_entry.is_started && queued_entry.priority < entry.priority)
+        .unwrap_or(entries.len());
+
+    entries.insert(position, entry);
+
+    position as u32
+}
+
+/// Cluster-wide queue of the Benchmarks, the single authoritative ordering of all the Benchmarks that did not finish yet.
+/// The queue is stored in a ConfigMap, every update is guarded by the resource version of the ConfigMap.
+/// Concurrent updates therefore conflict and are retried, so duplicate queue positions and gaps can't happen.
+/// The `queue_position` in the Benchmark status mirrors the position of the Benchmark in this queue.
+#[derive(Clone)]
+pub struct BenchmarkQueue {
+    k8s_client: Client,
+    config_map_api: Api<ConfigMap>,
+    config_map_name: String,
+}
+
+impl BenchmarkQueue {
+    pub fn new(k8s_client: Client) -> BenchmarkQueue {
+        BenchmarkQueue {
+            config_map_api: Api::<ConfigMap>::namespaced(k8s_client.clone(), &operator_namespace()),
+            config_map_name: env::var("QUEUE_CONFIG_MAP_NAME")
+                .unwrap_or("benchmark-operator-queue".to_string()),
+            k8s_client,
+        }
+    }
+
+    /// Returns the queue entries, first in queue first.
+    pub async fn entries(&self) -> Result<Vec<QueueEntry>, Error> {
+        match self.config_map_api.get_opt(&self.config_map_name).await? {
+            Some(config_map) => BenchmarkQueue::parse_entries(&config_map),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Creates the queue from the existing Benchmarks when it does not exist yet (e.g., when the operator was upgraded).
+    /// An existing queue drops the entries of the Benchmarks that no longer exist or already finished
+    /// (e.g., a Benchmark that was force deleted without its finalizer), such an entry would block the queue forever.
+    pub async fn init(&self, benchmarks: &[Benchmark]) -> Result<(), Error> {
+        let mut queued_benchmarks: Vec<&Benchmark> = benchmarks
+            .iter()
+            .filter(|benchmark| {
+                // Matrix Benchmarks are not queued, their runs are.
+                !benchmark.is_matrix()
+                    && benchmark
+                        .status
+                        .as_ref()
+                        .is_some_and(|status| !status.state.is_finished())
+            })
+            .collect();
+
+        if self
+            .config_map_api
+            .get_opt(&self.config_map_name)
+            .await?
+            .is_some()
+        {
+            let stale_entries = self
+                .update(|entries| {
+                    let (queued_entries, stale_entries) =
+                        entries.drain(..).partition(|entry: &QueueEntry| {
+                            queued_benchmarks.iter().any(|benchmark| {
+                                entry.is_benchmark(
+                                    &benchmark.namespace().unwrap_or("default".to_string()),
+                                    &benchmark.name_any(),
+                                )
+                            })
+                        });
+                    *entries = queued_entries;
+
+                    stale_entries
+                })
+                .await?;
+
+            if stale_entries.is_empty() {
+                return Ok(());
+            }
+
+            for entry in stale_entries.iter() {
+                warn!(
+                    "Removed Benchmark {} in namespace {} from the queue, it no longer exists or already finished.",
+                    entry.name, entry.namespace
+                );
+            }
+
+            return self.sync_queue_positions().await;
+        }
+
+        queued_benchmarks
+            .sort_by_key(|benchmark| benchmark.status.as_ref().unwrap().queue_position);
+
+        self.update(|entries| {
+            *entries = queued_benchmarks
+                .iter()
+                .map(|benchmark| {
+                    // Unwrap is safe, because the optional status of the benchmark was already checked.
+                    let status = benchmark.status.as_ref().unwrap();
+
+                    QueueEntry {
+                        namespace: benchmark.namespace().unwrap_or("default".to_string()),
+                        name: benchmark.name_any(),
+                        priority: benchmark.priority(),
+                        is_started: status.state != BenchmarkState::Pending
+                            || status.started_at.is_some(),
+                    }
+                })
+                .collect();
+        })
+        .await
+        .map(|_| ())
+    }
+
+    /// Adds a Benchmark to the queue and returns its queue position.
+    /// The Benchmark is queued behind the started Benchmarks and the Benchmarks with the same or a higher priority.
+    pub async fn enqueue(&self, benchmark: &Benchmark) -> Result<u32, Error> {
+        let namespace = benchmark.namespace().unwrap_or("default".to_string());
+        let name = benchmark.name_any();
+        let priority = benchmark.priority();
+
+        self.update(|entries| {
+            insert_entry(
+                entries,
+                QueueEntry {
+                    namespace: namespace.clone(),
+                    name: name.clone(),
+                    priority,
+                    is_started: false,
+                },
+            )
+        })
+        .await
+    }
+
+    /// Marks the Benchmark as started when it is first in queue.
+    /// Returns whether the Benchmark may start its workloads.
+    pub async fn try_start(&self, namespace: &str, name: &str) -> Result<bool, Error> {
+        self.update(|entries| match entries.first_mut() {
+            Some(entry) if entry.is_benchmark(namespace, name) => {
+                entry.is_started = true;
+                true
+            }
+            _ => false,
+        })
+        .await
+    }
+
+    /// Removes the Benchmark from the queue.
+    pub async fn remove(&self, namespace: &str, name: &str) -> Result<(), Error> {
+        self.update(|entries| entries.retain(|entry| !entry.is_benchmark(namespace, name)))
+            .await
+    }
+
+    /// Writes the position of every queued Benchmark to the `queue_position` of its status.
+    /// Largest queue position first, so the Benchmark that is first in queue is updated (and reconciled) last.
+    pub async fn sync_queue_positions(&self) -> Result<(), Error> {
+        for (position, entry) in self.entries().await?.iter().enumerate().rev() {
+            let benchmark_api =
+                Api::<Benchmark>::namespaced(self.k8s_client.clone(), &entry.namespace);
+
+            let status = json!({ "status": { "queuePosition": position } });
+
+            match benchmark_api
+                .patch_status(&entry.name, &PatchParams::default(), &Patch::Merge(&status))
+                .await
+            {
+                // The Benchmark might be deleted in the meantime.
+                Err(Error::Api(err)) if err.code == 404 => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies `update` to the queue entries and stores the result.
+    /// When another update happened in the meantime, the update is retried on the latest version of the queue.
+    async fn update<T>(
+        &self,
+        mut update: impl FnMut(&mut Vec<QueueEntry>) -> T,
+    ) -> Result<T, Error> {
+        loop {
+            let config_map = self.config_map_api.get_opt(&self.config_map_name).await?;
+
+            let mut entries = match &config_map {
+                Some(config_map) => BenchmarkQueue::parse_entries(config_map)?,
+                None => Vec::new(),
+            };
+            let entries_current = entries.clone();
+
+            let result = update(&mut entries);
+
+            if config_map.is_some() && entries == entries_current {
+                return Ok(result);
+            }
+
+            let data = BTreeMap::from([(
+                QUEUE_KEY.to_string(),
+                serde_json::to_string(&entries).map_err(Error::SerdeError)?,
+            )]);
+
+            let response = match config_map {
+                Some(mut config_map) => {
+                    // The ConfigMap keeps the resource version that was read, the replace fails with a conflict when it changed.
+                    config_map.data = Some(data);
+
+                    self.config_map_api
+                        .replace(&self.config_map_name, &PostParams::default(), &config_map)
+                        .await
+                }
+                None => {
+                    let config_map = ConfigMap {
+                        metadata: ObjectMeta {
+                            name: Some(self.config_map_name.clone()),
+                            ..Default::default()
+                        },
+                        data: Some(data),
+                        ..Default::default()
+                    };
+
+                    self.config_map_api
+                        .create(&PostParams::default(), &config_map)
+                        .await
+                }
+            };
+
+            match response {
+                Ok(_) => return Ok(result),
+                // Conflict (or already created by someone else), retry with the latest version of the queue.
+                Err(Error::Api(err)) if err.code == 409 => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    fn parse_entries(config_map: &ConfigMap) -> Result<Vec<QueueEntry>, Error> {
+        match config_map
+            .data
+            .as_ref()
+            .and_then(|data| data.get(QUEUE_KEY))
+        {
+            Some(queue) => serde_json::from_str(queue).map_err(Error::SerdeError),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, priority: i32) -> QueueEntry {
+        QueueEntry {
+            namespace: "default".into(),
+            name: name.into(),
+            priority,
+            is_started: false,
+        }
+    }
+
+    fn names(entries: &[QueueEntry]) -> Vec<&str> {
+        entries.iter().map(|entry| entry.name.as_str()).collect()
+    }
+
+    #[test]
+    fn higher_priority_is_queued_first() {
+        let mut entries = vec![entry("low", -1), entry("default", 0)];
+
+        assert_eq!(insert_entry(&mut entries, entry("high", 5)), 0);
+        assert_eq!(names(&entries), ["high", "low", "default"]);
+    }
+
+    #[test]
+    fn same_priority_is_queued_in_order() {
+        let mut entries = vec![entry("first", 1), entry("second", 0)];
+
+        assert_eq!(insert_entry(&mut entries, entry("third", 1)), 1);
+        assert_eq!(names(&entries), ["first", "third", "second"]);
+    }
+
+    #[test]
+    fn started_entries_are_not_moved_back() {
+        let mut entries = vec![
+            QueueEntry {
+                is_started: true,
+                ..entry("started", -5)
+            },
+            entry("pending", 0),
+        ];
+
+        assert_eq!(insert_entry(&mut entries, entry("urgent", 10)), 1);
+        assert_eq!(names(&entries), ["started", "urgent", "pending"]);
+    }
+
+    #[test]
+    fn queued_entry_keeps_its_position() {
+        let mut entries = vec![entry("first", 0), entry("second", 0)];
+
+        assert_eq!(insert_entry(&mut entries, entry("second", 10)), 1);
+        assert_eq!(names(&entries), ["first", "second"]);
+    }
+
+    #[test]
+    fn entries_are_matched_by_namespace_and_name() {
+        let mut entries = vec![entry("benchmark", 0)];
+
+        let other_namespace = QueueEntry {
+            namespace: "other".into(),
+            ..entry("benchmark", 0)
+        };
+
+        assert_eq!(insert_entry(&mut entries, other_namespace), 1);
+        assert_eq!(entries.len(), 2);
+    }
+}
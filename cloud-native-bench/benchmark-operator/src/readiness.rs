@@ -0,0 +1,127 @@
+use crate::benchmark_controller::{HttpReadiness, TcpReadiness};
+use hyper::{Body, Client, Uri};
+use hyper_openssl::HttpsConnector;
+use k8s_openapi::api::core::v1::Pod;
+use kube::{error::ErrorResponse, Error};
+use std::time::Duration;
+use tokio::{net::TcpStream, time::timeout};
+
+/// Maximum time a single readiness check may take.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Whether there are Pods and all of them have the `Ready` condition.
+pub fn pods_ready(pods: &[Pod]) -> bool {
+    !pods.is_empty()
+        && pods.iter().all(|pod| {
+            pod.status
+                .as_ref()
+                .and_then(|status| status.conditions.as_ref())
+                .is_some_and(|conditions| {
+                    conditions
+                        .iter()
+                        .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+                })
+        })
+}
+
+/// Whether a GET request to the URL (`http` or `https`) returns the expected status code.
+/// An invalid URL is an error, a server that is not reachable (yet) is not ready.
+pub async fn http_ready(http_readiness: &HttpReadiness) -> Result<bool, Error> {
+    let uri: Uri = http_readiness
+        .url
+        .parse()
+        .map_err(|err| readiness_error("Invalid readiness URL.", format!("{err}")))?;
+
+    if !matches!(uri.scheme_str(), Some("http" | "https")) || uri.host().is_none() {
+        return Err(readiness_error(
+            "Invalid readiness URL.",
+            format!(
+                "{} is not an http or https URL with a host.",
+                http_readiness.url
+            ),
+        ));
+    }
+
+    let connector = HttpsConnector::new().map_err(|err| {
+        readiness_error("Readiness check could not be prepared.", format!("{err}"))
+    })?;
+
+    match timeout(
+        CHECK_TIMEOUT,
+        Client::builder().build::<_, Body>(connector).get(uri),
+    )
+    .await
+    {
+        Ok(Ok(response)) => Ok(match http_readiness.status_code {
+            Some(status_code) => response.status().as_u16() == status_code,
+            None => response.status().is_success() || response.status().is_redirection(),
+        }),
+        // The server is not reachable (yet).
+        _ => Ok(false),
+    }
+}
+
+/// Whether a TCP connection to the port can be opened.
+pub async fn tcp_ready(tcp_readiness: &TcpReadiness) -> bool {
+    matches!(
+        timeout(
+            CHECK_TIMEOUT,
+            TcpStream::connect((tcp_readiness.host.as_str(), tcp_readiness.port)),
+        )
+        .await,
+        Ok(Ok(_))
+    )
+}
+
+fn readiness_error(message: &str, reason: String) -> Error {
+    Error::Api(ErrorResponse {
+        status: "Error".into(),
+        message: message.into(),
+        reason,
+        code: 400,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{PodCondition, PodStatus};
+
+    fn pod(is_ready: bool) -> Pod {
+        Pod {
+            status: Some(PodStatus {
+                conditions: Some(vec![PodCondition {
+                    type_: "Ready".into(),
+                    status: if is_ready { "True" } else { "False" }.into(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pods_ready_needs_every_pod_ready() {
+        assert!(pods_ready(&[pod(true), pod(true)]));
+        assert!(!pods_ready(&[pod(true), pod(false)]));
+        assert!(!pods_ready(&[Pod::default()]));
+    }
+
+    #[test]
+    fn pods_ready_needs_pods() {
+        assert!(!pods_ready(&[]));
+    }
+
+    #[tokio::test]
+    async fn http_ready_rejects_invalid_urls() {
+        for url in ["ftp://server/health", "server:8080/health", "not a url"] {
+            let http_readiness = HttpReadiness {
+                url: url.into(),
+                status_code: None,
+            };
+
+            assert!(http_ready(&http_readiness).await.is_err(), "{url}");
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use crate::{benchmark_controller::Benchmark, state::State};
+use benchmark_adapter_types::ResourceUsageSample;
+use benchmark_database_interface::DatabaseInterface;
+use k8s_openapi::{api::core::v1::Pod, http::Request};
+use kube::{error::ErrorResponse, Api, Client, Error, ResourceExt};
+use serde::Deserialize;
+use std::{collections::BTreeSet, env, time::Duration};
+use tracing::{info, warn};
+
+/// Summary API of the kubelet (`/stats/summary`), only the statistics that are sampled are deserialized.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct Summary {
+    pods: Vec<PodStats>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct PodStats {
+    pod_ref: PodReference,
+    containers: Vec<ContainerStats>,
+    cpu: Option<CpuStats>,
+    memory: Option<MemoryStats>,
+    network: Option<NetworkStats>,
+    #[serde(rename = "ephemeral-storage")]
+    ephemeral_storage: Option<FsStats>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct PodReference {
+    name: String,
+    namespace: String,
+    uid: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct ContainerStats {
+    name: String,
+    cpu: Option<CpuStats>,
+    memory: Option<MemoryStats>,
+    rootfs: Option<FsStats>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct CpuStats {
+    usage_nano_cores: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct MemoryStats {
+    working_set_bytes: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct NetworkStats {
+    rx_bytes: Option<u64>,
+    tx_bytes: Option<u64>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct FsStats {
+    used_bytes: Option<u64>,
+}
+
+/// Interval between two samples of the resource usage of the workload Pods, defaults to 10 seconds.
+fn sample_interval() -> Duration {
+    Duration::from_secs(
+        env::var("RESOURCE_USAGE_SAMPLE_INTERVAL_SECONDS")
+            .ok()
+            .and_then(|seconds| seconds.parse().ok())
+            .unwrap_or(10),
+    )
+}
+
+/// Entry-point to start sampling the resource usage of the workload Pods of the running Benchmark.
+/// The samples are saved in the database under the run of the Benchmark, which links them to the results of the run.
+pub async fn run(state: &State) {
+    let k8s_client = Client::try_default()
+        .await
+        .expect("Failed to create kube client.");
+
+    let sample_interval = sample_interval();
+
+    info!(
+        "Sampling the resource usage of running Benchmarks every {} seconds.",
+        sample_interval.as_secs()
+    );
+
+    let mut interval = tokio::time::interval(sample_interval);
+
+    loop {
+        interval.tick().await;
+
+        let (benchmark_name, namespace, is_sampled) = {
+            let state_data = state.state_data.read().await;
+
+            (
+                state_data.benchmark_name.clone(),
+                state_data.namespace.clone(),
+                state_data.is_leader && state_data.is_benchmark_running,
+            )
+        };
+
+        if !is_sampled {
+            continue;
+        }
+
+        // Sampling is a measurement aid, so a failed sample only logs a warning.
+        if let Err(err) = sample(k8s_client.clone(), &benchmark_name, &namespace).await {
+            warn!(
+                "Could not sample the resource usage of Benchmark {}: {}",
+                benchmark_name, err
+            );
+        }
+    }
+}
+
+/// Samples the resource usage of the workload Pods of the Benchmark and saves the samples in the database.
+async fn sample(k8s_client: Client, benchmark_name: &str, namespace: &str) -> Result<(), Error> {
+    let benchmark = Api::<Benchmark>::namespaced(k8s_client.clone(), namespace)
+        .get(benchmark_name)
+        .await?;
+
+    let Some(run_id) = benchmark
+        .status
+        .as_ref()
+        .and_then(|status| status.run_id.clone())
+    else {
+        return Ok(());
+    };
+
+    let pods = benchmark
+        .owned_pods(&Api::<Pod>::namespaced(
+            k8s_client.clone(),
+            &benchmark.workload_namespace(),
+        ))
+        .await?;
+
+    let pod_uids: BTreeSet<String> = pods.iter().filter_map(|pod| pod.uid()).collect();
+    let node_names: BTreeSet<String> = pods
+        .iter()
+        .filter_map(|pod| pod.spec.as_ref()?.node_name.clone())
+        .collect();
+
+    let mut resource_usage_samples = Vec::new();
+
+    for node_name in node_names {
+        let summary = node_summary(k8s_client.clone(), &node_name).await?;
+
+        for pod_stats in summary
+            .pods
+            .into_iter()
+            .filter(|pod_stats| pod_uids.contains(&pod_stats.pod_ref.uid))
+        {
+            resource_usage_samples.extend(resource_usage_samples_of(benchmark_name, pod_stats));
+        }
+    }
+
+    if resource_usage_samples.is_empty() {
+        return Ok(());
+    }
+
+    let database_url = env::var("DATABASE_URL").map_err(|err| sample_error(err.to_string()))?;
+
+    DatabaseInterface::save_resource_usage(database_url, run_id, resource_usage_samples)
+        .await
+        .map_err(|err| sample_error(err.to_string()))
+}
+
+/// Fetches the statistics of the kubelet of the node through the API server proxy.
+async fn node_summary(k8s_client: Client, node_name: &str) -> Result<Summary, Error> {
+    let request = Request::get(format!("/api/v1/nodes/{node_name}/proxy/stats/summary"))
+        .body(Vec::new())
+        .map_err(Error::HttpError)?;
+
+    k8s_client.request::<Summary>(request).await
+}
+
+/// A sample with the totals of the Pod and a sample per container.
+fn resource_usage_samples_of(
+    benchmark_name: &str,
+    pod_stats: PodStats,
+) -> Vec<ResourceUsageSample> {
+    let mut resource_usage_samples = vec![ResourceUsageSample {
+        benchmark_name: benchmark_name.into(),
+        namespace: pod_stats.pod_ref.namespace.clone(),
+        pod: pod_stats.pod_ref.name.clone(),
+        container: None,
+        cpu_usage_nano_cores: pod_stats
+            .cpu
+            .and_then(|cpu| cpu.usage_nano_cores)
+            .map(to_i64),
+        memory_working_set_bytes: pod_stats
+            .memory
+            .and_then(|memory| memory.working_set_bytes)
+            .map(to_i64),
+        network_rx_bytes: pod_stats
+            .network
+            .as_ref()
+            .and_then(|network| network.rx_bytes)
+            .map(to_i64),
+        network_tx_bytes: pod_stats
+            .network
+            .as_ref()
+            .and_then(|network| network.tx_bytes)
+            .map(to_i64),
+        disk_used_bytes: pod_stats
+            .ephemeral_storage
+            .and_then(|ephemeral_storage| ephemeral_storage.used_bytes)
+            .map(to_i64),
+    }];
+
+    for container_stats in pod_stats.containers {
+        resource_usage_samples.push(ResourceUsageSample {
+            benchmark_name: benchmark_name.into(),
+            namespace: pod_stats.pod_ref.namespace.clone(),
+            pod: pod_stats.pod_ref.name.clone(),
+            container: Some(container_stats.name),
+            cpu_usage_nano_cores: container_stats
+                .cpu
+                .and_then(|cpu| cpu.usage_nano_cores)
+                .map(to_i64),
+            memory_working_set_bytes: container_stats
+                .memory
+                .and_then(|memory| memory.working_set_bytes)
+                .map(to_i64),
+            network_rx_bytes: None,
+            network_tx_bytes: None,
+            disk_used_bytes: container_stats
+                .rootfs
+                .and_then(|rootfs| rootfs.used_bytes)
+                .map(to_i64),
+        });
+    }
+
+    resource_usage_samples
+}
+
+/// Postgres has no unsigned integers, the statistics never come close to the maximum of `i64`.
+fn to_i64(value: u64) -> i64 {
+    value.try_into().unwrap_or(i64::MAX)
+}
+
+fn sample_error(reason: String) -> Error {
+    Error::Api(ErrorResponse {
+        status: "Error".into(),
+        message: "Resource usage could not be saved.".into(),
+        reason,
+        code: 500,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Trimmed summary of a kubelet, the statistics that are not sampled are ignored.
+    fn summary() -> Summary {
+        serde_json::from_value(json!({
+            "node": { "nodeName": "node-1" },
+            "pods": [{
+                "podRef": { "name": "workload-0", "namespace": "benchmarks", "uid": "pod-uid" },
+                "startTime": "2024-01-01T00:00:00Z",
+                "containers": [
+                    {
+                        "name": "server",
+                        "cpu": { "time": "2024-01-01T00:01:00Z", "usageNanoCores": 250000000 },
+                        "memory": { "workingSetBytes": 52428800, "rssBytes": 41943040 },
+                        "rootfs": { "usedBytes": 4096 }
+                    },
+                    { "name": "sidecar" }
+                ],
+                "cpu": { "usageNanoCores": 300000000 },
+                "memory": { "workingSetBytes": 62914560 },
+                "network": { "name": "eth0", "rxBytes": 1024, "txBytes": 2048 },
+                "ephemeral-storage": { "usedBytes": 8192 }
+            }]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn summary_is_parsed() {
+        let summary = summary();
+
+        assert_eq!(summary.pods.len(), 1);
+        assert_eq!(summary.pods[0].pod_ref.uid, "pod-uid");
+        assert_eq!(summary.pods[0].containers.len(), 2);
+    }
+
+    #[test]
+    fn samples_hold_pod_totals_and_containers() {
+        let pod_stats = summary().pods.remove(0);
+        let samples = resource_usage_samples_of("benchmark", pod_stats);
+
+        assert_eq!(samples.len(), 3);
+
+        let pod_sample = &samples[0];
+        assert_eq!(pod_sample.benchmark_name, "benchmark");
+        assert_eq!(pod_sample.namespace, "benchmarks");
+        assert_eq!(pod_sample.pod, "workload-0");
+        assert_eq!(pod_sample.container, None);
+        assert_eq!(pod_sample.cpu_usage_nano_cores, Some(300000000));
+        assert_eq!(pod_sample.memory_working_set_bytes, Some(62914560));
+        assert_eq!(pod_sample.network_rx_bytes, Some(1024));
+        assert_eq!(pod_sample.network_tx_bytes, Some(2048));
+        assert_eq!(pod_sample.disk_used_bytes, Some(8192));
+
+        let container_sample = &samples[1];
+        assert_eq!(container_sample.container.as_deref(), Some("server"));
+        assert_eq!(container_sample.cpu_usage_nano_cores, Some(250000000));
+        assert_eq!(container_sample.memory_working_set_bytes, Some(52428800));
+        assert_eq!(container_sample.network_rx_bytes, None);
+        assert_eq!(container_sample.disk_used_bytes, Some(4096));
+    }
+
+    #[test]
+    fn missing_statistics_are_empty() {
+        let pod_stats = summary().pods.remove(0);
+        let samples = resource_usage_samples_of("benchmark", pod_stats);
+
+        let sidecar_sample = &samples[2];
+        assert_eq!(sidecar_sample.container.as_deref(), Some("sidecar"));
+        assert_eq!(sidecar_sample.cpu_usage_nano_cores, None);
+        assert_eq!(sidecar_sample.memory_working_set_bytes, None);
+        assert_eq!(sidecar_sample.disk_used_bytes, None);
+    }
+
+    #[test]
+    fn large_values_are_clamped() {
+        assert_eq!(to_i64(u64::MAX), i64::MAX);
+    }
+}
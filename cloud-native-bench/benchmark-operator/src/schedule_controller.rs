@@ -0,0 +1,525 @@
+use crate::{
+    benchmark_controller::{
+        child_name, install_crd, set_condition, Benchmark, BenchmarkSpec, BenchmarkState,
+        MAX_NAME_LENGTH,
+    },
+    events::publish_event,
+    state::{Context, State},
+};
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use futures::StreamExt;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{Condition, Time};
+use kube::{
+    api::{DeleteParams, ListParams, Patch, PatchParams, PostParams},
+    core::ObjectMeta,
+    runtime::{controller::Action, events::EventType, watcher, Controller},
+    Api, Client, CustomResource, CustomResourceExt, Error, Resource, ResourceExt,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, str::FromStr, sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+
+/// Label that is added to the Benchmarks created by a BenchmarkSchedule, its value is the UID of the BenchmarkSchedule.
+pub const SCHEDULE_LABEL: &str = "michiel.van.kenhove.ugent.be/benchmark-schedule";
+
+/// How to treat a scheduled Benchmark when the previous Benchmark did not finish yet.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq, Eq)]
+pub enum ConcurrencyPolicy {
+    #[default]
+    Allow, // Queue the new Benchmark behind the unfinished Benchmarks.
+    Forbid,  // Skip the new Benchmark.
+    Replace, // Delete the unfinished Benchmarks and queue the new Benchmark.
+}
+
+/// BenchmarkSchedule CRD spec, creates Benchmarks from a template on a cron schedule.
+#[derive(CustomResource, Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
+#[kube(
+    group = "michiel.van.kenhove.ugent.be",
+    version = "v1",
+    kind = "BenchmarkSchedule",
+    status = "BenchmarkScheduleStatus",
+    shortname = "benchsched",
+    category = "all",
+    printcolumn = r#"{"name": "Schedule", "type": "string", "jsonPath": ".spec.schedule"}"#,
+    printcolumn = r#"{"name": "Suspend", "type": "boolean", "jsonPath": ".spec.suspend"}"#,
+    printcolumn = r#"{"name": "Last Schedule", "type": "date", "jsonPath": ".status.lastScheduleTime"}"#,
+    printcolumn = r#"{"name": "Age", "type": "date", "jsonPath": ".metadata.creationTimestamp"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkScheduleSpec {
+    /// Cron expression in UTC, e.g., `0 2 * * *` for every night at 2:00 or `0 2 * * 1-5` for every weekday.
+    /// A leading seconds field (and a trailing year field) are supported as well.
+    pub schedule: String,
+    /// Spec of the Benchmarks that are created, they are queued like any other Benchmark.
+    pub benchmark_template: BenchmarkSpec,
+    #[serde(default)]
+    pub concurrency_policy: ConcurrencyPolicy,
+    /// Number of finished Benchmarks that completed to keep, defaults to 3.
+    pub successful_history_limit: Option<u32>,
+    /// Number of finished Benchmarks that failed or were cancelled to keep, defaults to 1.
+    pub failed_history_limit: Option<u32>,
+    /// Suspended schedules don't create Benchmarks.
+    #[serde(default)]
+    pub suspend: bool,
+}
+
+/// Kubernetes CR status object.
+#[derive(Deserialize, Serialize, Clone, Default, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BenchmarkScheduleStatus {
+    pub last_schedule_time: Option<Time>, // Time the last Benchmark was scheduled.
+    pub active: Vec<String>,              // Names of the Benchmarks that did not finish yet.
+    pub conditions: Vec<Condition>, // Kubernetes-style conditions, see `CONDITION_SCHEDULE_VALID`.
+}
+
+/// Condition that is `False` when the cron expression of the schedule is invalid, its message tells why.
+pub const CONDITION_SCHEDULE_VALID: &str = "ScheduleValid";
+
+impl BenchmarkSchedule {
+    pub async fn reconcile(&self, ctx: Arc<Context>) -> Result<Action, Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
+
+        let schedule_api = Api::<BenchmarkSchedule>::namespaced(ctx.k8s_client.clone(), &namespace);
+        let benchmark_api = Api::<Benchmark>::namespaced(ctx.k8s_client.clone(), &namespace);
+
+        let mut status = self.status.clone().unwrap_or_default();
+
+        let cron_schedule = match self.cron_schedule() {
+            Ok(cron_schedule) => cron_schedule,
+            Err(err) => {
+                let message = format!("Invalid schedule {:?}: {}", self.spec.schedule, err);
+                warn!(
+                    "BenchmarkSchedule {} has an invalid schedule: {}",
+                    name, err
+                );
+
+                // Only publish the Event when the schedule became invalid, not on every reconciliation.
+                let is_reported = status.conditions.iter().any(|condition| {
+                    condition.type_ == CONDITION_SCHEDULE_VALID
+                        && condition.status == "False"
+                        && condition.message == message
+                });
+
+                if !is_reported {
+                    publish_event(
+                        ctx.k8s_client.clone(),
+                        self.object_ref(&()),
+                        EventType::Warning,
+                        "InvalidSchedule",
+                        &message,
+                        None,
+                    )
+                    .await;
+                }
+
+                set_condition(
+                    &mut status.conditions,
+                    CONDITION_SCHEDULE_VALID,
+                    false,
+                    "InvalidSchedule",
+                    &message,
+                );
+
+                schedule_api
+                    .patch_status(
+                        &name,
+                        &PatchParams::default(),
+                        &Patch::Merge(&json!({ "status": status })),
+                    )
+                    .await?;
+
+                return Ok(Action::await_change());
+            }
+        };
+
+        set_condition(
+            &mut status.conditions,
+            CONDITION_SCHEDULE_VALID,
+            true,
+            "ValidSchedule",
+            "The schedule is a valid cron expression.",
+        );
+
+        let mut benchmarks = benchmark_api
+            .list(&ListParams::default().labels(&format!(
+                "{}={}",
+                SCHEDULE_LABEL,
+                self.uid().unwrap_or_default()
+            )))
+            .await?
+            .items;
+        benchmarks.sort_by_key(|benchmark| benchmark.creation_timestamp());
+
+        let (mut active, finished): (Vec<Benchmark>, Vec<Benchmark>) =
+            benchmarks.into_iter().partition(|benchmark| {
+                !benchmark
+                    .status
+                    .as_ref()
+                    .is_some_and(|status| status.state.is_finished())
+            });
+
+        self.remove_history(&benchmark_api, finished).await?;
+
+        let now = Utc::now();
+
+        // Only the most recent missed schedule time is run, like a CronJob does.
+        let last_schedule_time = self
+            .status
+            .as_ref()
+            .and_then(|status| status.last_schedule_time.clone())
+            .or(self.creation_timestamp())
+            .map(|time| time.0)
+            .unwrap_or(now);
+        let schedule_time = cron_schedule
+            .after(&last_schedule_time)
+            .take_while(|time| *time <= now)
+            .last();
+
+        if let (Some(schedule_time), false) = (schedule_time, self.spec.suspend) {
+            status.last_schedule_time = Some(Time(schedule_time));
+
+            match &self.spec.concurrency_policy {
+                ConcurrencyPolicy::Forbid if !active.is_empty() => {
+                    info!(
+                        "BenchmarkSchedule {} skipped a run, the previous Benchmark did not finish yet.",
+                        name
+                    );
+                }
+                concurrency_policy => {
+                    if *concurrency_policy == ConcurrencyPolicy::Replace {
+                        for benchmark in active.drain(..) {
+                            info!(
+                                "BenchmarkSchedule {} replaces Benchmark {}.",
+                                name,
+                                benchmark.name_any()
+                            );
+
+                            // The finalizer of the Benchmark removes its workloads and moves the queue.
+                            benchmark_api
+                                .delete(&benchmark.name_any(), &DeleteParams::background())
+                                .await?;
+                        }
+                    }
+
+                    let benchmark = self.scheduled_benchmark(schedule_time);
+
+                    info!(
+                        "BenchmarkSchedule {} creates Benchmark {}.",
+                        name,
+                        benchmark.name_any()
+                    );
+
+                    match benchmark_api
+                        .create(&PostParams::default(), &benchmark)
+                        .await
+                    {
+                        Ok(benchmark) => active.push(benchmark),
+                        // Already created for this schedule time.
+                        Err(Error::Api(err)) if err.code == 409 => {}
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+        }
+
+        status.active = active
+            .iter()
+            .map(|benchmark| benchmark.name_any())
+            .collect();
+
+        schedule_api
+            .patch_status(
+                &name,
+                &PatchParams::default(),
+                &Patch::Merge(&json!({ "status": status })),
+            )
+            .await?;
+
+        // Requeue at the next schedule time.
+        match cron_schedule.after(&now).next() {
+            Some(next_time) => Ok(Action::requeue(
+                (next_time - now).to_std().unwrap_or_default() + Duration::from_secs(1),
+            )),
+            None => Ok(Action::await_change()),
+        }
+    }
+
+    /// Parses the cron expression, a standard cron expression (without seconds) runs at the start of the minute.
+    /// The days of the week are numbered like standard cron (i.e., 0 and 7 are Sunday).
+    pub fn cron_schedule(&self) -> Result<Schedule, cron::error::Error> {
+        let mut fields: Vec<String> = self
+            .spec
+            .schedule
+            .split_whitespace()
+            .map(String::from)
+            .collect();
+
+        if fields.len() == 5 {
+            fields.insert(0, "0".into());
+        }
+
+        if let Some(day_of_week) = fields.get_mut(5) {
+            *day_of_week = translate_day_of_week(day_of_week);
+        }
+
+        Schedule::from_str(&fields.join(" "))
+    }
+
+    /// Deletes the oldest finished Benchmarks that exceed the history limits.
+    async fn remove_history(
+        &self,
+        benchmark_api: &Api<Benchmark>,
+        finished: Vec<Benchmark>,
+    ) -> Result<(), Error> {
+        let (successful, failed): (Vec<Benchmark>, Vec<Benchmark>) =
+            finished.into_iter().partition(|benchmark| {
+                benchmark
+                    .status
+                    .as_ref()
+                    .is_some_and(|status| status.state == BenchmarkState::Completed)
+            });
+
+        let successful_history_limit = self.spec.successful_history_limit.unwrap_or(3) as usize;
+        let failed_history_limit = self.spec.failed_history_limit.unwrap_or(1) as usize;
+
+        // The Benchmarks are sorted oldest first.
+        let removed = successful
+            .iter()
+            .rev()
+            .skip(successful_history_limit)
+            .chain(failed.iter().rev().skip(failed_history_limit));
+
+        for benchmark in removed {
+            info!(
+                "BenchmarkSchedule {} removes Benchmark {} from its history.",
+                self.name_any(),
+                benchmark.name_any()
+            );
+
+            match benchmark_api
+                .delete(&benchmark.name_any(), &DeleteParams::background())
+                .await
+            {
+                // Already removed.
+                Err(Error::Api(err)) if err.code == 404 => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Benchmark for `schedule_time`, named `<schedule name>-<schedule time in minutes>` like the Jobs of a CronJob.
+    fn scheduled_benchmark(&self, schedule_time: DateTime<Utc>) -> Benchmark {
+        let benchmark_name = child_name(
+            &self.name_any(),
+            &format!("-{}", schedule_time.timestamp() / 60),
+            MAX_NAME_LENGTH,
+        );
+
+        let mut benchmark = Benchmark::new(&benchmark_name, self.spec.benchmark_template.clone());
+
+        benchmark.metadata = ObjectMeta {
+            name: Some(benchmark_name),
+            namespace: self.namespace(),
+            labels: Some(BTreeMap::from([(
+                SCHEDULE_LABEL.into(),
+                self.uid().unwrap_or_default(),
+            )])),
+            owner_references: Some(vec![self.controller_owner_ref(&()).unwrap()]),
+            ..Default::default()
+        };
+
+        benchmark
+    }
+}
+
+/// Translates a standard cron day of the week field (0 to 7, Sunday is 0 and 7) to the numbering of the `cron` crate
+/// (1 to 7, Sunday is 1). The numeric days are listed one by one, so a range that ends on Sunday (e.g., `5-7`) works as well.
+/// Names (e.g., `MON-FRI`), `*` and `?` are kept, invalid days are kept so the parser reports them.
+fn translate_day_of_week(field: &str) -> String {
+    field
+        .split(',')
+        .map(|item| {
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, step.parse::<usize>().ok()),
+                None => (item, Some(1)),
+            };
+
+            let bounds = match (range, range.split_once('-')) {
+                ("*" | "?", _) if !item.contains('/') => return item.to_string(),
+                ("*" | "?", _) => Some((0, 6)),
+                (_, Some((start, end))) => start.parse::<u32>().ok().zip(end.parse().ok()),
+                // A single day with a step (e.g., `1/2`) runs until the end of the week.
+                (_, None) if item.contains('/') => range.parse().ok().map(|start| (start, 6)),
+                (_, None) => range.parse().ok().map(|day| (day, day)),
+            };
+
+            let (Some((start, end)), Some(step)) = (bounds, step) else {
+                return item.to_string();
+            };
+
+            if start > end || end > 7 || step == 0 {
+                return item.to_string();
+            }
+
+            (start..=end)
+                .step_by(step)
+                .map(|day| (day % 7 + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Entry-point to start the BenchmarkSchedule controller.
+pub async fn run(state: &State) {
+    let k8s_client = Client::try_default()
+        .await
+        .expect("Failed to create kube client.");
+
+    install_crd(k8s_client.clone(), BenchmarkSchedule::crd()).await;
+
+    let ctx = state.create_context(k8s_client.clone());
+
+    Controller::new(
+        Api::<BenchmarkSchedule>::all(k8s_client.clone()),
+        watcher::Config::default(),
+    )
+    // Watch the created Benchmarks, so the history and the active Benchmarks are updated when a Benchmark finishes.
+    .owns(
+        Api::<Benchmark>::all(k8s_client),
+        watcher::Config::default().labels(SCHEDULE_LABEL),
+    )
+    .shutdown_on_signal()
+    .run(reconcile, error_policy, ctx)
+    .for_each(|res| async move {
+        match res {
+            Ok(o) => info!("reconciled {o:?}"),
+            Err(e) => error!("reconcile failed: {e:?}"),
+        }
+    })
+    .await;
+}
+
+async fn reconcile(schedule: Arc<BenchmarkSchedule>, ctx: Arc<Context>) -> Result<Action, Error> {
+    ctx.metrics.count_reconciliation("benchmark_schedule");
+
+    schedule.reconcile(ctx).await
+}
+
+fn error_policy(_schedule: Arc<BenchmarkSchedule>, _error: &Error, ctx: Arc<Context>) -> Action {
+    ctx.metrics.count_reconcile_error("benchmark_schedule");
+
+    Action::requeue(Duration::from_secs(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, TimeZone, Timelike};
+
+    fn schedule(schedule: &str) -> BenchmarkSchedule {
+        BenchmarkSchedule::new(
+            "schedule",
+            BenchmarkScheduleSpec {
+                schedule: schedule.into(),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn next_time(schedule: &Schedule) -> DateTime<Utc> {
+        schedule
+            .after(&Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+            .next()
+            .unwrap()
+    }
+
+    #[test]
+    fn five_fields_run_at_the_start_of_the_minute() {
+        let cron_schedule = schedule("30 2 * * *").cron_schedule().unwrap();
+
+        assert_eq!(
+            next_time(&cron_schedule),
+            Utc.with_ymd_and_hms(2024, 1, 1, 2, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn six_fields_start_with_seconds() {
+        let cron_schedule = schedule(" 15 30 2 * * * ").cron_schedule().unwrap();
+
+        let next_time = next_time(&cron_schedule);
+        assert_eq!(
+            (next_time.hour(), next_time.minute(), next_time.second()),
+            (2, 30, 15)
+        );
+    }
+
+    #[test]
+    fn seven_fields_end_with_the_year() {
+        let cron_schedule = schedule("0 30 2 * * * 2025").cron_schedule().unwrap();
+
+        assert_eq!(
+            next_time(&cron_schedule),
+            Utc.with_ymd_and_hms(2025, 1, 1, 2, 30, 0).unwrap()
+        );
+    }
+
+    fn next_days(schedule_expression: &str, count: usize) -> Vec<u32> {
+        // Saturday.
+        let start = Utc.with_ymd_and_hms(2024, 1, 6, 12, 0, 0).unwrap();
+
+        schedule(schedule_expression)
+            .cron_schedule()
+            .unwrap()
+            .after(&start)
+            .take(count)
+            .map(|time| time.day())
+            .collect()
+    }
+
+    #[test]
+    fn weekdays_are_numbered_like_standard_cron() {
+        // Monday 8 to Friday 12 January 2024, then Monday 15.
+        assert_eq!(next_days("0 2 * * 1-5", 6), [8, 9, 10, 11, 12, 15]);
+        assert_eq!(next_days("0 0 2 * * 1-5", 6), [8, 9, 10, 11, 12, 15]);
+    }
+
+    #[test]
+    fn sunday_is_zero_and_seven() {
+        assert_eq!(next_days("0 2 * * 0", 2), [7, 14]);
+        assert_eq!(next_days("0 2 * * 7", 2), [7, 14]);
+        assert_eq!(next_days("0 2 * * 5-7", 4), [7, 12, 13, 14]);
+    }
+
+    #[test]
+    fn day_of_week_lists_and_steps_are_translated() {
+        assert_eq!(next_days("0 2 * * 1,3", 3), [8, 10, 15]);
+        // Sunday, Tuesday, Thursday and Saturday.
+        assert_eq!(next_days("0 2 * * */2", 4), [7, 9, 11, 13]);
+    }
+
+    #[test]
+    fn day_of_week_names_are_kept() {
+        assert_eq!(next_days("0 2 * * MON-FRI", 6), [8, 9, 10, 11, 12, 15]);
+        assert_eq!(next_days("0 2 * * SUN", 2), [7, 14]);
+    }
+
+    #[test]
+    fn invalid_expressions_are_rejected() {
+        assert!(schedule("every night").cron_schedule().is_err());
+        assert!(schedule("61 * * * *").cron_schedule().is_err());
+        assert!(schedule("").cron_schedule().is_err());
+        assert!(schedule("0 2 * * 8").cron_schedule().is_err());
+    }
+}
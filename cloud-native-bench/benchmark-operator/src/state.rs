@@ -1,7 +1,11 @@
-use std::sync::Arc;
+use std::{
+    env,
+    sync::{Arc, OnceLock},
+};
 
+use crate::{benchmark_controller::Benchmark, metrics::Metrics, queue::BenchmarkQueue};
 use chrono::{DateTime, Utc};
-use kube::Client;
+use kube::{runtime::reflector::Store, Client};
 use serde::Serialize;
 use tokio::sync::RwLock;
 
@@ -14,6 +18,7 @@ pub struct StateData {
     pub namespace: String,
     pub is_benchmark_running: bool,
     pub is_benchmark_done: bool,
+    pub is_leader: bool, // Only the leader replica reconciles Benchmarks and accepts gRPC calls.
 }
 
 impl Default for StateData {
@@ -24,6 +29,7 @@ impl Default for StateData {
             namespace: Default::default(),
             is_benchmark_running: false,
             is_benchmark_done: false,
+            is_leader: false,
         }
     }
 }
@@ -40,6 +46,13 @@ impl StateData {
     pub fn clear_state(&mut self) {
         self.new_benchmark(Default::default(), Default::default())
     }
+
+    /// Clears the state only when it belongs to the given Benchmark.
+    pub fn clear_benchmark(&mut self, benchmark_name: &str, namespace: &str) {
+        if self.benchmark_name == benchmark_name && self.namespace == namespace {
+            self.clear_state();
+        }
+    }
 }
 
 /// Context for the reconciler.
@@ -50,6 +63,12 @@ pub struct Context {
 
     /// Shared state between all the components of the operator.
     pub state_data: Arc<RwLock<StateData>>,
+
+    /// Cluster-wide queue of the Benchmarks.
+    pub queue: BenchmarkQueue,
+
+    /// Metrics of the operator.
+    pub metrics: Arc<Metrics>,
 }
 
 impl Context {
@@ -71,13 +90,26 @@ impl Context {
 pub struct State {
     /// Shared state between all the components of the operator.
     pub state_data: Arc<RwLock<StateData>>,
+
+    /// Metrics of the operator, exposed by the webserver.
+    pub metrics: Arc<Metrics>,
+
+    /// Benchmarks cached by the Benchmark controller, set once the controller runs (i.e., on the leader).
+    pub benchmark_store: Arc<OnceLock<Store<Benchmark>>>,
 }
 
 impl State {
     pub fn create_context(&self, k8s_client: Client) -> Arc<Context> {
         Arc::new(Context {
+            queue: BenchmarkQueue::new(k8s_client.clone()),
             k8s_client,
             state_data: self.state_data.clone(),
+            metrics: self.metrics.clone(),
         })
     }
 }
+
+/// Namespace the operator runs in, provided by the downward API when running in the cluster.
+pub fn operator_namespace() -> String {
+    env::var("POD_NAMESPACE").unwrap_or("default".to_string())
+}
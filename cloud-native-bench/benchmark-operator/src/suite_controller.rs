@@ -0,0 +1,472 @@
+use crate::{
+    benchmark_controller::{
+        child_name, install_crd, Benchmark, BenchmarkSpec, BenchmarkState, MAX_NAME_LENGTH,
+    },
+    state::{Context, State},
+};
+use benchmark_adapter_types::SuiteRun;
+use benchmark_database_interface::DatabaseInterface;
+use chrono::Utc;
+use futures::StreamExt;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+use kube::{
+    api::{ListParams, Patch, PatchParams, PostParams},
+    core::ObjectMeta,
+    runtime::{controller::Action, watcher, Controller},
+    Api, Client, CustomResource, CustomResourceExt, Error, Resource, ResourceExt,
+};
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{Schema, SingleOrVec},
+    JsonSchema,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, env, sync::Arc, time::Duration};
+use tracing::{error, info, warn};
+
+/// Label that is added to the Benchmarks of a BenchmarkSuite, its value is the UID of the BenchmarkSuite (i.e., the suite run ID).
+pub const SUITE_LABEL: &str = "michiel.van.kenhove.ugent.be/benchmark-suite";
+
+/// Annotation that holds the name of the BenchmarkSuite of a Benchmark, names don't fit in a label value.
+pub const SUITE_NAME_ANNOTATION: &str = "michiel.van.kenhove.ugent.be/benchmark-suite-name";
+
+/// Maximum number of Benchmarks of a suite, every Benchmark embeds the validation rules of a Benchmark spec,
+/// so their cost is multiplied by this number.
+const MAX_SUITE_BENCHMARKS: u32 = 50;
+
+/// Adds the CRD validation rule that requires exactly one of `spec` and `benchmarkRef` to the Benchmarks of a suite.
+fn suite_benchmarks_schema(gen: &mut SchemaGenerator) -> Schema {
+    let mut schema = gen.subschema_for::<Vec<SuiteBenchmark>>().into_object();
+
+    let array = schema.array();
+    array.min_items = Some(1);
+    array.max_items = Some(MAX_SUITE_BENCHMARKS);
+
+    if let Some(SingleOrVec::Single(items)) = &mut array.items {
+        if let Schema::Object(items) = items.as_mut() {
+            items.extensions.insert(
+                "x-kubernetes-validations".into(),
+                json!([{
+                    "rule": "has(self.spec) != has(self.benchmarkRef)",
+                    "message": "Exactly one of spec or benchmarkRef must be set."
+                }]),
+            );
+        }
+    }
+
+    Schema::Object(schema)
+}
+
+/// A Benchmark of a suite, either embedded or a reference to an existing Benchmark.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiteBenchmark {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<BenchmarkSpec>,
+    /// Name of an existing Benchmark in the namespace of the suite (e.g., a Benchmark that ran before), its spec is run again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub benchmark_ref: Option<String>,
+}
+
+/// BenchmarkSuite CRD spec, runs a set of Benchmarks one after the other as one unit.
+#[derive(CustomResource, Debug, Serialize, Deserialize, Default, Clone, JsonSchema)]
+#[kube(
+    group = "michiel.van.kenhove.ugent.be",
+    version = "v1",
+    kind = "BenchmarkSuite",
+    status = "BenchmarkSuiteStatus",
+    shortname = "benchsuite",
+    category = "all",
+    printcolumn = r#"{"name": "State", "type": "string", "jsonPath": ".status.state"}"#,
+    printcolumn = r#"{"name": "Started", "type": "date", "jsonPath": ".status.startedAt", "priority": 1}"#,
+    printcolumn = r#"{"name": "Finished", "type": "date", "jsonPath": ".status.finishedAt", "priority": 1}"#,
+    printcolumn = r#"{"name": "Age", "type": "date", "jsonPath": ".metadata.creationTimestamp"}"#,
+    namespaced
+)]
+#[serde(rename_all = "camelCase")]
+pub struct BenchmarkSuiteSpec {
+    /// Benchmarks of the suite, each Benchmark is queued once the previous Benchmark finished.
+    /// The Benchmarks are named `<suite name>-<index>` and owned by the suite.
+    #[schemars(schema_with = "suite_benchmarks_schema")]
+    pub benchmarks: Vec<SuiteBenchmark>,
+    /// Keeps queueing the next Benchmarks when a Benchmark fails or is cancelled, by default the suite stops and is marked `Failed`.
+    #[serde(default)]
+    pub continue_on_failure: bool,
+    /// Cancels the suite when set, its Benchmark that did not finish is cancelled and the next Benchmarks are not queued.
+    #[serde(default)]
+    pub cancel: bool,
+    /// Deletes the suite and its Benchmarks once the suite is finished for this many seconds, the `ttlSecondsAfterFinished`
+    /// of its Benchmarks is ignored. The results in the database are kept. Defaults to the operator-wide default, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds_after_finished: Option<u64>,
+}
+
+/// State of a Benchmark of a suite.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiteBenchmarkStatus {
+    pub name: String,
+    pub state: Option<BenchmarkState>, // State of the Benchmark, `None` until the Benchmark is created and reconciled.
+}
+
+/// Kubernetes CR status object.
+#[derive(Deserialize, Serialize, Clone, Default, Debug, JsonSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BenchmarkSuiteStatus {
+    pub state: BenchmarkState, // `Pending` until the first Benchmark is created, `Running` until the suite finished.
+    pub run_id: Option<String>, // Identifies the suite run in the database, the runs of its Benchmarks are linked to it.
+    pub started_at: Option<Time>, // Time at which the first Benchmark of the suite was created.
+    pub finished_at: Option<Time>, // Time at which the suite reached a final state.
+    pub message: Option<String>, // Tells why the suite ended.
+    pub benchmarks: Vec<SuiteBenchmarkStatus>, // The Benchmarks of the suite, in the order of the spec.
+}
+
+impl BenchmarkSuiteStatus {
+    /// Moves the suite to a final `state`, `message` tells why the suite ended.
+    fn finish(&mut self, state: BenchmarkState, message: &str) {
+        self.state = state;
+        self.finished_at = Some(Time(Utc::now()));
+        self.message = Some(message.into());
+    }
+}
+
+impl BenchmarkSuite {
+    pub async fn reconcile(&self, ctx: Arc<Context>) -> Result<Action, Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
+
+        let suite_api = Api::<BenchmarkSuite>::namespaced(ctx.k8s_client.clone(), &namespace);
+        let benchmark_api = Api::<Benchmark>::namespaced(ctx.k8s_client.clone(), &namespace);
+
+        let benchmark_names: Vec<String> = (0..self.spec.benchmarks.len())
+            .map(|index| self.suite_benchmark_name(index))
+            .collect();
+
+        let mut benchmarks = benchmark_api
+            .list(&ListParams::default().labels(&format!(
+                "{}={}",
+                SUITE_LABEL,
+                self.uid().unwrap_or_default()
+            )))
+            .await?
+            .items;
+
+        // The runs of a matrix Benchmark of the suite carry the suite label as well, they are left out by their name.
+        benchmarks.retain(|benchmark| benchmark_names.contains(&benchmark.name_any()));
+
+        let mut status = self.status.clone().unwrap_or_default();
+
+        // Once the suite finished, only the states of its Benchmarks are kept up to date.
+        let is_finished = status.state.is_finished();
+
+        if self.spec.cancel && !is_finished {
+            for benchmark in benchmarks.iter().filter(|benchmark| {
+                !benchmark.spec.cancel
+                    && !benchmark
+                        .status
+                        .as_ref()
+                        .is_some_and(|status| status.state.is_finished())
+            }) {
+                info!(
+                    "Cancelling Benchmark {} of BenchmarkSuite {}.",
+                    benchmark.name_any(),
+                    name
+                );
+
+                benchmark_api
+                    .patch(
+                        &benchmark.name_any(),
+                        &PatchParams::default(),
+                        &Patch::Merge(&json!({ "spec": { "cancel": true } })),
+                    )
+                    .await?;
+            }
+
+            status.finish(
+                BenchmarkState::Cancelled,
+                "The suite was cancelled, its Benchmark that did not finish is cancelled as well.",
+            );
+        } else if !is_finished {
+            self.progress(&benchmark_api, &mut benchmarks, &mut status)
+                .await?;
+        }
+
+        status.benchmarks = benchmark_names
+            .into_iter()
+            .map(|benchmark_name| SuiteBenchmarkStatus {
+                state: benchmarks
+                    .iter()
+                    .find(|benchmark| benchmark.name_any() == benchmark_name)
+                    .and_then(|benchmark| benchmark.status.as_ref())
+                    .map(|status| status.state.clone()),
+                name: benchmark_name,
+            })
+            .collect();
+
+        suite_api
+            .patch_status(
+                &name,
+                &PatchParams::default(),
+                &Patch::Merge(&json!({ "status": status })),
+            )
+            .await?;
+
+        // Finished suites are deleted (with their Benchmarks) once their time to live expired.
+        Ok(self
+            .delete_expired(ctx.k8s_client.clone())
+            .await?
+            .unwrap_or(Action::await_change()))
+    }
+
+    /// Creates the next Benchmark of the suite once the previous Benchmark finished, and finishes the suite after its last Benchmark.
+    async fn progress(
+        &self,
+        benchmark_api: &Api<Benchmark>,
+        benchmarks: &mut Vec<Benchmark>,
+        status: &mut BenchmarkSuiteStatus,
+    ) -> Result<(), Error> {
+        let name = self.name_any();
+
+        for (index, suite_benchmark) in self.spec.benchmarks.iter().enumerate() {
+            let benchmark_name = self.suite_benchmark_name(index);
+
+            let Some(benchmark) = benchmarks
+                .iter()
+                .find(|benchmark| benchmark.name_any() == benchmark_name)
+            else {
+                let spec = match (&suite_benchmark.spec, &suite_benchmark.benchmark_ref) {
+                    (Some(spec), None) => spec.clone(),
+                    (None, Some(benchmark_ref)) => match benchmark_api.get(benchmark_ref).await {
+                        Ok(benchmark) => benchmark.spec,
+                        Err(Error::Api(err)) if err.code == 404 => {
+                            status.finish(
+                                BenchmarkState::Failed,
+                                &format!("Benchmark {index} of the suite references Benchmark {benchmark_ref}, which does not exist."),
+                            );
+                            return Ok(());
+                        }
+                        Err(err) => return Err(err),
+                    },
+                    _ => {
+                        status.finish(
+                            BenchmarkState::Failed,
+                            &format!("Benchmark {index} of the suite needs exactly one of spec or benchmarkRef."),
+                        );
+                        return Ok(());
+                    }
+                };
+
+                info!(
+                    "BenchmarkSuite {} creates Benchmark {}.",
+                    name, benchmark_name
+                );
+
+                match benchmark_api
+                    .create(
+                        &PostParams::default(),
+                        &self.suite_benchmark(&benchmark_name, spec),
+                    )
+                    .await
+                {
+                    Ok(benchmark) => benchmarks.push(benchmark),
+                    // Created in the meantime.
+                    Err(Error::Api(err)) if err.code == 409 => {}
+                    Err(err) => return Err(err),
+                }
+
+                if status.state == BenchmarkState::Pending {
+                    status.state = BenchmarkState::Running;
+                    status.run_id = self.uid();
+                    status.started_at = Some(Time(Utc::now()));
+                }
+
+                return Ok(());
+            };
+
+            match benchmark.status.as_ref().map(|status| &status.state) {
+                Some(BenchmarkState::Completed) => {}
+                Some(state) if state.is_finished() => {
+                    if !self.spec.continue_on_failure {
+                        status.finish(
+                            BenchmarkState::Failed,
+                            &format!("Benchmark {benchmark_name} of the suite did not complete."),
+                        );
+                        return Ok(());
+                    }
+                }
+                // The Benchmark did not finish yet.
+                _ => return Ok(()),
+            }
+        }
+
+        let benchmark_count = self.spec.benchmarks.len();
+        let completed_count = benchmarks
+            .iter()
+            .filter(|benchmark| {
+                benchmark
+                    .status
+                    .as_ref()
+                    .is_some_and(|status| status.state == BenchmarkState::Completed)
+            })
+            .count();
+
+        if completed_count >= benchmark_count {
+            status.finish(
+                BenchmarkState::Completed,
+                &format!("All {benchmark_count} Benchmarks completed."),
+            );
+        } else {
+            status.finish(
+                BenchmarkState::Failed,
+                &format!(
+                    "{} of the {} Benchmarks did not complete.",
+                    benchmark_count - completed_count,
+                    benchmark_count
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Name of the Benchmark at `index`, `<suite name>-<index>` limited to 253 characters.
+    fn suite_benchmark_name(&self, index: usize) -> String {
+        child_name(&self.name_any(), &format!("-{index}"), MAX_NAME_LENGTH)
+    }
+
+    /// Benchmark of this suite that runs `spec`.
+    fn suite_benchmark(&self, benchmark_name: &str, spec: BenchmarkSpec) -> Benchmark {
+        let mut benchmark = Benchmark::new(
+            benchmark_name,
+            BenchmarkSpec {
+                cancel: false,
+                ..spec
+            },
+        );
+
+        benchmark.metadata = ObjectMeta {
+            name: Some(benchmark_name.into()),
+            namespace: self.namespace(),
+            labels: Some(BTreeMap::from([(
+                SUITE_LABEL.into(),
+                self.uid().unwrap_or_default(),
+            )])),
+            annotations: Some(BTreeMap::from([(
+                SUITE_NAME_ANNOTATION.into(),
+                self.name_any(),
+            )])),
+            owner_references: Some(vec![self.controller_owner_ref(&()).unwrap()]),
+            ..Default::default()
+        };
+
+        benchmark
+    }
+}
+
+/// Linking the runs of Benchmarks to the run of their BenchmarkSuite.
+impl Benchmark {
+    /// Run ID of the BenchmarkSuite this Benchmark is part of, if any.
+    pub fn suite_run_id(&self) -> Option<String> {
+        self.labels().get(SUITE_LABEL).cloned()
+    }
+
+    /// Labels and annotations that tie the Benchmarks created by this Benchmark (i.e., matrix runs) to its suite.
+    pub fn suite_metadata(&self) -> (BTreeMap<String, String>, BTreeMap<String, String>) {
+        let labels = self
+            .labels()
+            .iter()
+            .filter(|(key, _)| *key == SUITE_LABEL)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        let annotations = self
+            .annotations()
+            .iter()
+            .filter(|(key, _)| *key == SUITE_NAME_ANNOTATION)
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        (labels, annotations)
+    }
+
+    /// Records the run of this Benchmark under the run of its suite, so the results of the suite can be shown together.
+    /// Failing to record the run does not fail the Benchmark.
+    pub async fn save_suite_run(&self) {
+        let Some(suite_run_id) = self.suite_run_id() else {
+            return;
+        };
+
+        let name = self.name_any();
+
+        let Ok(database_url) = env::var("DATABASE_URL") else {
+            warn!("DATABASE_URL environment variable was not set, the suite run of Benchmark {} is not saved.", name);
+            return;
+        };
+
+        let suite_run = SuiteRun {
+            suite_run_id,
+            suite_name: self
+                .annotations()
+                .get(SUITE_NAME_ANNOTATION)
+                .cloned()
+                .unwrap_or_default(),
+            namespace: self.namespace().unwrap_or("default".to_string()),
+            benchmark_name: name.clone(),
+        };
+
+        if let Err(err) = DatabaseInterface::save_suite_run(
+            database_url,
+            self.uid().unwrap_or_default(),
+            suite_run,
+        )
+        .await
+        {
+            warn!(
+                "Could not save the suite run of Benchmark {}: {}",
+                name, err
+            );
+        }
+    }
+}
+
+/// Entry-point to start the BenchmarkSuite controller.
+pub async fn run(state: &State) {
+    let k8s_client = Client::try_default()
+        .await
+        .expect("Failed to create kube client.");
+
+    install_crd(k8s_client.clone(), BenchmarkSuite::crd()).await;
+
+    let ctx = state.create_context(k8s_client.clone());
+
+    Controller::new(
+        Api::<BenchmarkSuite>::all(k8s_client.clone()),
+        watcher::Config::default(),
+    )
+    // Watch the Benchmarks of the suites, so the next Benchmark is queued when a Benchmark finishes.
+    .owns(
+        Api::<Benchmark>::all(k8s_client),
+        watcher::Config::default().labels(SUITE_LABEL),
+    )
+    .shutdown_on_signal()
+    .run(reconcile, error_policy, ctx)
+    .for_each(|res| async move {
+        match res {
+            Ok(o) => info!("reconciled {o:?}"),
+            Err(e) => error!("reconcile failed: {e:?}"),
+        }
+    })
+    .await;
+}
+
+async fn reconcile(suite: Arc<BenchmarkSuite>, ctx: Arc<Context>) -> Result<Action, Error> {
+    ctx.metrics.count_reconciliation("benchmark_suite");
+
+    suite.reconcile(ctx).await
+}
+
+fn error_policy(_suite: Arc<BenchmarkSuite>, _error: &Error, ctx: Arc<Context>) -> Action {
+    ctx.metrics.count_reconcile_error("benchmark_suite");
+
+    Action::requeue(Duration::from_secs(60))
+}
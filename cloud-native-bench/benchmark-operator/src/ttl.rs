@@ -0,0 +1,210 @@
+use crate::{
+    benchmark_controller::Benchmark,
+    events::publish_event,
+    matrix::MATRIX_PARENT_LABEL,
+    suite_controller::{BenchmarkSuite, SUITE_LABEL},
+};
+use chrono::Utc;
+use k8s_openapi::{apimachinery::pkg::apis::meta::v1::Time, NamespaceResourceScope};
+use kube::{
+    api::DeleteParams,
+    runtime::{controller::Action, events::EventType},
+    Api, Client, Error, Resource, ResourceExt,
+};
+use serde::de::DeserializeOwned;
+use std::{env, fmt::Debug, time::Duration};
+use tracing::info;
+
+/// Removing finished Benchmarks once their time to live expired.
+impl Benchmark {
+    /// Time a finished Benchmark is kept, the spec overrides the `BENCHMARK_TTL_SECONDS_AFTER_FINISHED` default of the operator.
+    /// `None` keeps the Benchmark until it is deleted by hand.
+    pub fn ttl_after_finished(&self) -> Option<Duration> {
+        ttl_after_finished(self.spec.ttl_seconds_after_finished)
+    }
+
+    /// Deletes this Benchmark when it finished and its time to live expired, the results in the database are kept.
+    /// Returns when to check again while the Benchmark is finished and its time to live did not expire yet,
+    /// `None` when the time to live does not apply to this Benchmark.
+    pub async fn delete_expired(&self, k8s_client: Client) -> Result<Option<Action>, Error> {
+        let Some(status) = &self.status else {
+            return Ok(None);
+        };
+
+        if !status.state.is_finished() {
+            return Ok(None);
+        }
+
+        if self.is_removed_with_owner() {
+            return Ok(None);
+        }
+
+        let Some(ttl) = self.ttl_after_finished() else {
+            return Ok(None);
+        };
+
+        // The finalizer of the Benchmark removes what is left of its workloads.
+        delete_expired(k8s_client, self, status.finished_at.clone(), ttl).await
+    }
+
+    /// The runs of a matrix Benchmark and the Benchmarks of a suite are removed together with their owner,
+    /// the owner would create them again when they are removed before it finished.
+    /// A suite has a time to live of its own, see `BenchmarkSuite::delete_expired`.
+    fn is_removed_with_owner(&self) -> bool {
+        self.labels().contains_key(MATRIX_PARENT_LABEL) || self.labels().contains_key(SUITE_LABEL)
+    }
+}
+
+/// Removing finished BenchmarkSuites (and their Benchmarks) once their time to live expired.
+impl BenchmarkSuite {
+    /// Time a finished suite is kept, the spec overrides the `BENCHMARK_TTL_SECONDS_AFTER_FINISHED` default of the operator.
+    /// `None` keeps the suite until it is deleted by hand.
+    pub fn ttl_after_finished(&self) -> Option<Duration> {
+        ttl_after_finished(self.spec.ttl_seconds_after_finished)
+    }
+
+    /// Deletes this suite when it finished and its time to live expired, its Benchmarks are deleted with it.
+    /// The results in the database are kept. Returns when to check again while the suite is finished
+    /// and its time to live did not expire yet, `None` when the time to live does not apply to this suite.
+    pub async fn delete_expired(&self, k8s_client: Client) -> Result<Option<Action>, Error> {
+        let Some(status) = &self.status else {
+            return Ok(None);
+        };
+
+        if !status.state.is_finished() {
+            return Ok(None);
+        }
+
+        let Some(ttl) = self.ttl_after_finished() else {
+            return Ok(None);
+        };
+
+        // The Benchmarks are owned by the suite, the background deletion removes them after the suite.
+        delete_expired(k8s_client, self, status.finished_at.clone(), ttl).await
+    }
+}
+
+/// Time to live of the spec, defaults to the `BENCHMARK_TTL_SECONDS_AFTER_FINISHED` of the operator.
+fn ttl_after_finished(ttl_seconds_after_finished: Option<u64>) -> Option<Duration> {
+    ttl_seconds_after_finished
+        .or_else(|| {
+            env::var("BENCHMARK_TTL_SECONDS_AFTER_FINISHED")
+                .ok()
+                .and_then(|seconds| seconds.parse().ok())
+        })
+        .map(Duration::from_secs)
+}
+
+/// Deletes the finished `object` once `ttl` passed since `finished_at` (or its creation, when the finish time is unknown).
+/// Returns when to check again while the time to live did not expire yet.
+async fn delete_expired<K>(
+    k8s_client: Client,
+    object: &K,
+    finished_at: Option<Time>,
+    ttl: Duration,
+) -> Result<Option<Action>, Error>
+where
+    K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+        + Clone
+        + Debug
+        + DeserializeOwned,
+{
+    let Some(finished_at) = finished_at.or_else(|| object.creation_timestamp()) else {
+        return Ok(None);
+    };
+
+    let age = (Utc::now() - finished_at.0).to_std().unwrap_or_default();
+
+    if age < ttl {
+        return Ok(Some(Action::requeue(ttl - age)));
+    }
+
+    let kind = K::kind(&());
+    let name = object.name_any();
+
+    info!(
+        "Deleting {} {}, it finished {} seconds ago.",
+        kind,
+        name,
+        age.as_secs()
+    );
+
+    publish_event(
+        k8s_client.clone(),
+        object.object_ref(&()),
+        EventType::Normal,
+        "Expired",
+        &format!(
+            "The {} finished more than {} seconds ago, it is deleted.",
+            kind,
+            ttl.as_secs()
+        ),
+        None,
+    )
+    .await;
+
+    match Api::<K>::namespaced(
+        k8s_client,
+        &object.namespace().unwrap_or("default".to_string()),
+    )
+    .delete(&name, &DeleteParams::background())
+    .await
+    {
+        // Already removed.
+        Err(Error::Api(err)) if err.code == 404 => {}
+        result => {
+            result?;
+        }
+    }
+
+    Ok(Some(Action::await_change()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark_controller::BenchmarkSpec;
+
+    fn benchmark(ttl_seconds_after_finished: Option<u64>) -> Benchmark {
+        Benchmark::new(
+            "benchmark",
+            BenchmarkSpec {
+                ttl_seconds_after_finished,
+                ..Default::default()
+            },
+        )
+    }
+
+    // The default is read from the environment of the process, so both cases are checked in one test.
+    #[test]
+    fn spec_overrides_default_ttl() {
+        env::set_var("BENCHMARK_TTL_SECONDS_AFTER_FINISHED", "3600");
+
+        assert_eq!(
+            benchmark(Some(60)).ttl_after_finished(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            benchmark(None).ttl_after_finished(),
+            Some(Duration::from_secs(3600))
+        );
+
+        env::remove_var("BENCHMARK_TTL_SECONDS_AFTER_FINISHED");
+
+        assert_eq!(benchmark(None).ttl_after_finished(), None);
+    }
+
+    #[test]
+    fn matrix_and_suite_children_are_removed_with_owner() {
+        assert!(!benchmark(None).is_removed_with_owner());
+
+        for label in [MATRIX_PARENT_LABEL, SUITE_LABEL] {
+            let mut child = benchmark(Some(60));
+            child
+                .labels_mut()
+                .insert(label.to_string(), "owner".to_string());
+
+            assert!(child.is_removed_with_owner());
+        }
+    }
+}
@@ -1,8 +1,17 @@
-use axum::{extract, routing::get, Json, Router};
+use axum::{
+    extract,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
 use tokio::signal;
 use tracing::info;
 
-use crate::state::{State, StateData};
+use crate::{
+    metrics,
+    state::{State, StateData},
+};
 
 // TODO: this web server will probably be removed from the operator and moved to a separate microservice
 
@@ -11,6 +20,8 @@ pub async fn spawn(state: &State) {
 
     let app = Router::new()
         .route("/status", get(get_status))
+        .route("/readyz", get(get_readyz))
+        .route("/metrics", get(get_metrics))
         .with_state(state.clone());
 
     axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
@@ -28,6 +39,23 @@ async fn get_status(extract::State(state): extract::State<State>) -> Json<StateD
     Json(state_data)
 }
 
+/// Only the leader replica is ready, so the gRPC Service only routes to the leader.
+async fn get_readyz(extract::State(state): extract::State<State>) -> StatusCode {
+    if state.state_data.read().await.is_leader {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// Metrics of the operator in the Prometheus text format.
+async fn get_metrics(extract::State(state): extract::State<State>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        metrics::render(&state).await,
+    )
+}
+
 // Adapted from: https://github.com/tokio-rs/axum/blob/6377dbebc0db0e4e204c38dd84dc457c062146d1/examples/graceful-shutdown/src/main.rs
 async fn shutdown_signal() {
     let ctrl_c = async {
@@ -0,0 +1,1297 @@
+use crate::{
+    benchmark_controller::{
+        child_name, Benchmark, BenchmarkWorkload, HelmSpec, WorkloadFailure, WorkloadStatus,
+        BENCHMARK_UID_LABEL, BENCHMARK_WORKLOAD_LABEL,
+    },
+    helm, readiness,
+};
+use benchmark_adapter_types::WorkloadLog;
+use benchmark_database_interface::DatabaseInterface;
+use k8s_openapi::{
+    api::{
+        apps::v1::{Deployment, StatefulSet},
+        batch::v1::Job,
+        core::v1::{ContainerStatus, EnvVar, ObjectReference, Pod, PodTemplateSpec, Service},
+    },
+    NamespaceResourceScope,
+};
+use kube::{
+    api::{DeleteParams, DynamicObject, ListParams, LogParams, PostParams},
+    core::{
+        gvk::{GroupVersion, GroupVersionKind},
+        ObjectMeta,
+    },
+    discovery::{self, Scope},
+    error::ErrorResponse,
+    runtime::events::EventType,
+    Api, Client, Error, Resource, ResourceExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{collections::BTreeMap, env, fmt::Debug};
+use tracing::{info, warn};
+
+/// Creating and removing the workloads of a Benchmark.
+impl Benchmark {
+    /// Creates the workloads whose dependencies are ready, until no more workloads can be created.
+    /// The created workloads are recorded in the status, returns whether all workloads are created.
+    pub async fn create_ready_workloads(&self, k8s_client: Client) -> Result<bool, Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+        let name = self.name_any();
+
+        // The latest status, so a workload is never created twice.
+        let mut workload_statuses = Api::<Benchmark>::namespaced(k8s_client.clone(), &namespace)
+            .get_status(&name)
+            .await?
+            .status
+            .map(|status| status.workloads)
+            .unwrap_or_default();
+
+        // The namespace of an isolated Benchmark is created before its first workload.
+        if self.is_isolated() && workload_statuses.is_empty() {
+            self.create_workload_namespace(k8s_client.clone()).await?;
+
+            self.publish_event(
+                k8s_client.clone(),
+                EventType::Normal,
+                "NamespaceCreated",
+                &format!(
+                    "Created namespace {} for the workloads.",
+                    self.workload_namespace()
+                ),
+                None,
+            )
+            .await;
+        }
+
+        loop {
+            let mut is_changed = false;
+
+            for (index, workload) in self.spec.workloads.iter().enumerate() {
+                let workload_name = self.workload_name(workload, index);
+
+                match workload_statuses
+                    .iter_mut()
+                    .find(|workload_status| workload_status.name == workload_name)
+                {
+                    Some(workload_status) => {
+                        if workload_status.ready
+                            || !self
+                                .is_workload_ready(k8s_client.clone(), index, workload)
+                                .await?
+                        {
+                            continue;
+                        }
+
+                        info!("Workload {} of Benchmark {} is ready.", workload_name, name);
+                        workload_status.ready = true;
+
+                        self.publish_event(
+                            k8s_client.clone(),
+                            EventType::Normal,
+                            "WorkloadReady",
+                            &format!("Workload {workload_name} is ready."),
+                            None,
+                        )
+                        .await;
+                    }
+                    None => {
+                        let is_dependencies_ready = workload.depends_on.iter().all(|dependency| {
+                            workload_statuses.iter().any(|workload_status| {
+                                &workload_status.name == dependency && workload_status.ready
+                            })
+                        });
+
+                        if !is_dependencies_ready {
+                            continue;
+                        }
+
+                        info!("Creating workload {} of Benchmark {}.", workload_name, name);
+                        let object_reference = self
+                            .create_workload(k8s_client.clone(), index, workload)
+                            .await?;
+
+                        let note = match &workload.helm_chart {
+                            Some(helm_spec) => format!(
+                                "Installed Helm release {} of workload {}.",
+                                self.helm_release_name(helm_spec, index),
+                                workload_name
+                            ),
+                            None => format!("Created workload {workload_name}."),
+                        };
+                        self.publish_event(
+                            k8s_client.clone(),
+                            EventType::Normal,
+                            "WorkloadCreated",
+                            &note,
+                            object_reference,
+                        )
+                        .await;
+
+                        workload_statuses.push(WorkloadStatus {
+                            name: workload_name,
+                            ready: workload.readiness.is_none(),
+                        });
+                    }
+                }
+
+                is_changed = true;
+
+                let workloads = workload_statuses.clone();
+                Benchmark::update_status(k8s_client.clone(), &name, &namespace, |status| {
+                    status.workloads = workloads
+                })
+                .await?;
+            }
+
+            // Workloads that became ready might unblock other workloads.
+            if !is_changed {
+                break;
+            }
+        }
+
+        Ok(workload_statuses.len() == self.spec.workloads.len())
+    }
+
+    /// Whether the readiness condition of the workload at `index` is met, a waited on Helm chart also needs ready Pods.
+    async fn is_workload_ready(
+        &self,
+        k8s_client: Client,
+        index: usize,
+        workload: &BenchmarkWorkload,
+    ) -> Result<bool, Error> {
+        // A Helm chart that is waited on needs ready Pods, even without a readiness condition.
+        let is_helm_wait = workload
+            .helm_chart
+            .as_ref()
+            .is_some_and(|helm_spec| helm_spec.wait);
+
+        let pods_ready = workload
+            .readiness
+            .as_ref()
+            .is_some_and(|readiness| readiness.pods_ready);
+
+        if pods_ready || is_helm_wait {
+            let namespace = self.workload_namespace();
+
+            // The Pods of a Helm chart are not labelled by the operator, Helm charts label them with the release name instead.
+            let label_selector = match &workload.helm_chart {
+                Some(helm_spec) => format!(
+                    "app.kubernetes.io/instance={}",
+                    self.helm_release_name(helm_spec, index)
+                ),
+                None => format!(
+                    "{}={},{}={}",
+                    BENCHMARK_UID_LABEL,
+                    self.uid().unwrap_or_default(),
+                    BENCHMARK_WORKLOAD_LABEL,
+                    index
+                ),
+            };
+
+            let pods = Api::<Pod>::namespaced(k8s_client, &namespace)
+                .list(&ListParams::default().labels(&label_selector))
+                .await?
+                .items;
+
+            if !readiness::pods_ready(&pods) {
+                return Ok(false);
+            }
+        }
+
+        let Some(workload_readiness) = &workload.readiness else {
+            return Ok(true);
+        };
+
+        if let Some(http_readiness) = &workload_readiness.http_get {
+            if !readiness::http_ready(http_readiness).await? {
+                return Ok(false);
+            }
+        }
+
+        if let Some(tcp_readiness) = &workload_readiness.tcp_socket {
+            if !readiness::tcp_ready(tcp_readiness).await {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Checks that this Benchmark has workloads and that every workload has exactly one workload kind.
+    pub fn validate_workloads(&self) -> Result<(), String> {
+        if self.spec.workloads.is_empty() {
+            return Err("The Benchmark has no workloads.".into());
+        }
+
+        for (index, workload) in self.spec.workloads.iter().enumerate() {
+            let kind_count = workload.kind_count();
+
+            if kind_count != 1 {
+                return Err(format!(
+                    "Workload {} needs exactly one workload kind, it has {}.",
+                    self.workload_name(workload, index),
+                    kind_count
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the workloads only depend on existing workloads and that there are no circular dependencies.
+    pub fn validate_dependencies(&self) -> Result<(), String> {
+        let workload_names: Vec<String> = self
+            .spec
+            .workloads
+            .iter()
+            .enumerate()
+            .map(|(index, workload)| self.workload_name(workload, index))
+            .collect();
+
+        for (index, workload_name) in workload_names.iter().enumerate() {
+            if workload_names[..index].contains(workload_name) {
+                return Err(format!(
+                    "Workload name {workload_name} is used more than once."
+                ));
+            }
+        }
+
+        for (workload, workload_name) in self.spec.workloads.iter().zip(workload_names.iter()) {
+            if let Some(dependency) = workload
+                .depends_on
+                .iter()
+                .find(|dependency| !workload_names.contains(dependency))
+            {
+                return Err(format!(
+                    "Workload {workload_name} depends on unknown workload {dependency}."
+                ));
+            }
+        }
+
+        // Resolve the workloads in the order they can be created, the unresolved workloads have circular dependencies.
+        let mut resolved_names: Vec<&String> = Vec::new();
+
+        loop {
+            let resolved_count = resolved_names.len();
+
+            for (workload, workload_name) in self.spec.workloads.iter().zip(workload_names.iter()) {
+                if !resolved_names.contains(&workload_name)
+                    && workload
+                        .depends_on
+                        .iter()
+                        .all(|dependency| resolved_names.contains(&dependency))
+                {
+                    resolved_names.push(workload_name);
+                }
+            }
+
+            if resolved_names.len() == resolved_count {
+                break;
+            }
+        }
+
+        if resolved_names.len() < workload_names.len() {
+            return Err("The workloads have circular dependencies.".into());
+        }
+
+        Ok(())
+    }
+
+    /// Name of the workload at `index`, which is used in `depends_on`, defaults to `<benchmark name>-<workload index>`.
+    pub fn workload_name(&self, workload: &BenchmarkWorkload, index: usize) -> String {
+        workload
+            .name
+            .clone()
+            .unwrap_or(format!("{}-{}", self.name_any(), index))
+    }
+
+    /// Creates the workload at `index` of this Benchmark, returns a reference to the created object (Helm releases have none).
+    pub async fn create_workload(
+        &self,
+        k8s_client: Client,
+        index: usize,
+        workload: &BenchmarkWorkload,
+    ) -> Result<Option<ObjectReference>, Error> {
+        let namespace = self.workload_namespace();
+
+        if let Some(pod_template) = &workload.pod_template {
+            // Workload is a regular container image.
+            let mut pod_template = pod_template.clone();
+            self.label_pod_template(&mut pod_template, index);
+            self.inject_workload_env(&mut pod_template, index);
+
+            let mut metadata = pod_template.metadata.unwrap_or_default();
+
+            // Pods get a generated name, unless the workload is named.
+            match &workload.name {
+                Some(workload_name) => metadata.name = Some(workload_name.clone()),
+                None => metadata.generate_name = Some(format!("{}-", self.name_any())),
+            }
+
+            let pod = Pod {
+                metadata: ObjectMeta {
+                    namespace: Some(namespace.clone()),
+                    owner_references: self.workload_owner_references(),
+                    ..metadata
+                },
+                spec: pod_template.spec,
+                ..Default::default()
+            };
+
+            self.create_owned(k8s_client, pod).await.map(Some)
+        } else if let Some(deployment_spec) = &workload.deployment {
+            let mut spec = deployment_spec.clone();
+            self.label_pod_template(&mut spec.template, index);
+            self.inject_workload_env(&mut spec.template, index);
+
+            let deployment = Deployment {
+                metadata: self.workload_metadata(workload, index),
+                spec: Some(spec),
+                ..Default::default()
+            };
+
+            self.create_owned(k8s_client, deployment).await.map(Some)
+        } else if let Some(stateful_set_spec) = &workload.stateful_set {
+            let mut spec = stateful_set_spec.clone();
+            self.label_pod_template(&mut spec.template, index);
+            self.inject_workload_env(&mut spec.template, index);
+
+            let stateful_set = StatefulSet {
+                metadata: self.workload_metadata(workload, index),
+                spec: Some(spec),
+                ..Default::default()
+            };
+
+            self.create_owned(k8s_client, stateful_set).await.map(Some)
+        } else if let Some(job_spec) = &workload.job {
+            let mut spec = job_spec.clone();
+            self.label_pod_template(&mut spec.template, index);
+            self.inject_workload_env(&mut spec.template, index);
+
+            let job = Job {
+                metadata: self.workload_metadata(workload, index),
+                spec: Some(spec),
+                ..Default::default()
+            };
+
+            self.create_owned(k8s_client, job).await.map(Some)
+        } else if let Some(service_spec) = &workload.service {
+            let service = Service {
+                metadata: self.workload_metadata(workload, index),
+                spec: Some(service_spec.clone()),
+                ..Default::default()
+            };
+
+            self.create_owned(k8s_client, service).await.map(Some)
+        } else if let Some(manifest) = &workload.manifest {
+            self.create_manifest(k8s_client, index, manifest)
+                .await
+                .map(Some)
+        } else if let Some(helm_spec) = &workload.helm_chart {
+            // Workload is a helm chart.
+            let release_name = self.helm_release_name(helm_spec, index);
+            let benchmark_namespace = self.namespace().unwrap_or("default".to_string());
+
+            // A release that exists but is not recorded by this Benchmark belongs to someone else (e.g., a fixed `releaseName`),
+            // installing over it would make this Benchmark uninstall it afterwards.
+            let is_recorded =
+                Api::<Benchmark>::namespaced(k8s_client.clone(), &benchmark_namespace)
+                    .get_status(&self.name_any())
+                    .await?
+                    .helm_releases()
+                    .contains(&release_name);
+
+            if !is_recorded && helm::release_exists(&namespace, &release_name).await? {
+                return Err(workload_error(format!(
+                    "Helm release {release_name} already exists in namespace {namespace} and is not owned by this Benchmark."
+                )));
+            }
+
+            // Record the Helm release before installing it, so a partially installed release is removed afterwards as well.
+            Benchmark::update_status(
+                k8s_client.clone(),
+                &self.name_any(),
+                &benchmark_namespace,
+                |status| {
+                    if !status.helm_releases.contains(&release_name) {
+                        status.helm_releases.push(release_name.clone())
+                    }
+                },
+            )
+            .await?;
+
+            // The values ConfigMap lives next to the Benchmark, not in the namespace of an isolated Benchmark.
+            helm::install(
+                k8s_client,
+                &namespace,
+                &benchmark_namespace,
+                &release_name,
+                helm_spec,
+            )
+            .await
+            .map(|_| None)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Creates an object that is owned by this Benchmark in the namespace of its workloads.
+    async fn create_owned<K>(&self, k8s_client: Client, object: K) -> Result<ObjectReference, Error>
+    where
+        K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+            + Clone
+            + Debug
+            + Serialize
+            + DeserializeOwned,
+    {
+        let namespace = self.workload_namespace();
+
+        Api::<K>::namespaced(k8s_client, &namespace)
+            .create(&PostParams::default(), &object)
+            .await
+            .map(|object| object.object_ref(&()))
+    }
+
+    /// Creates an object from an inline manifest, the created object is recorded in the status so it can be removed afterwards.
+    async fn create_manifest(
+        &self,
+        k8s_client: Client,
+        index: usize,
+        manifest: &serde_json::Value,
+    ) -> Result<ObjectReference, Error> {
+        let namespace = self.workload_namespace();
+
+        let mut object: DynamicObject =
+            serde_json::from_value(manifest.clone()).map_err(Error::SerdeError)?;
+
+        let gvk = object
+            .types
+            .as_ref()
+            .and_then(|types| GroupVersionKind::try_from(types).ok())
+            .ok_or_else(|| workload_error("Manifest has no valid apiVersion and kind.".into()))?;
+
+        let (api_resource, capabilities) = discovery::pinned_kind(&k8s_client, &gvk).await?;
+
+        object.labels_mut().extend(self.workload_labels(index));
+
+        let object_api = if capabilities.scope == Scope::Namespaced {
+            // Only namespaced objects can be owned by the Benchmark.
+            object.metadata.namespace = Some(namespace.clone());
+            object
+                .owner_references_mut()
+                .extend(self.workload_owner_references().unwrap_or_default());
+
+            Api::<DynamicObject>::namespaced_with(k8s_client.clone(), &namespace, &api_resource)
+        } else {
+            Api::<DynamicObject>::all_with(k8s_client.clone(), &api_resource)
+        };
+
+        let object = object_api.create(&PostParams::default(), &object).await?;
+
+        let object_reference = ObjectReference {
+            api_version: Some(api_resource.api_version),
+            kind: Some(api_resource.kind),
+            name: object.metadata.name,
+            namespace: object.metadata.namespace,
+            uid: object.metadata.uid,
+            ..Default::default()
+        };
+
+        Benchmark::update_status(
+            k8s_client,
+            &self.name_any(),
+            &self.namespace().unwrap_or("default".to_string()),
+            |status| status.manifests.push(object_reference.clone()),
+        )
+        .await?;
+
+        Ok(object_reference)
+    }
+
+    /// Metadata of a workload object that is owned by this Benchmark.
+    fn workload_metadata(&self, workload: &BenchmarkWorkload, index: usize) -> ObjectMeta {
+        ObjectMeta {
+            name: Some(self.workload_name(workload, index)),
+            namespace: Some(self.workload_namespace()),
+            labels: Some(self.workload_labels(index)),
+            owner_references: self.workload_owner_references(),
+            ..Default::default()
+        }
+    }
+
+    /// Labels of the workload at `index`, so the objects of this Benchmark can be found and removed afterwards.
+    fn workload_labels(&self, index: usize) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            (BENCHMARK_UID_LABEL.into(), self.uid().unwrap_or_default()),
+            (BENCHMARK_WORKLOAD_LABEL.into(), index.to_string()),
+        ])
+    }
+
+    /// Label the Pods of the workload at `index`, so the Pods of this Benchmark can be found and removed afterwards.
+    fn label_pod_template(&self, pod_template: &mut PodTemplateSpec, index: usize) {
+        pod_template
+            .metadata
+            .get_or_insert_with(Default::default)
+            .labels
+            .get_or_insert_with(Default::default)
+            .extend(self.workload_labels(index));
+    }
+
+    /// Environment variables that tell the containers of the workload at `index` which Benchmark run they are part of:
+    /// the address of the operator gRPC server (when the operator knows it), the name and namespace of the Benchmark,
+    /// its run ID, the workload index, the run ID of its suite (if any) and a `MATRIX_<KEY>` variable per matrix parameter of the run.
+    pub fn workload_env(&self, index: usize) -> Vec<EnvVar> {
+        let env_var = |name: &str, value: String| EnvVar {
+            name: name.into(),
+            value: Some(value),
+            ..Default::default()
+        };
+
+        let mut workload_env = Vec::new();
+
+        if let Ok(operator_grpc_address) = env::var("OPERATOR_GRPC_ADDRESS") {
+            workload_env.push(env_var("OPERATOR_GRPC_ADDRESS", operator_grpc_address));
+        }
+
+        workload_env.extend([
+            env_var("BENCHMARK_NAME", self.name_any()),
+            env_var(
+                "BENCHMARK_NAMESPACE",
+                self.namespace().unwrap_or("default".to_string()),
+            ),
+            // The run of a Benchmark is identified by its UID.
+            env_var("BENCHMARK_RUN_ID", self.uid().unwrap_or_default()),
+            env_var("BENCHMARK_WORKLOAD_INDEX", index.to_string()),
+        ]);
+
+        if let Some(suite_run_id) = self.suite_run_id() {
+            workload_env.push(env_var("BENCHMARK_SUITE_RUN_ID", suite_run_id));
+        }
+
+        for (key, value) in self.matrix_parameters() {
+            workload_env.push(env_var(&matrix_env_name(&key), value));
+        }
+
+        workload_env
+    }
+
+    /// Adds the environment variables of the workload at `index` to every container of the Pods.
+    /// Variables that a container already sets are kept, so the spec can override them.
+    fn inject_workload_env(&self, pod_template: &mut PodTemplateSpec, index: usize) {
+        let Some(pod_spec) = &mut pod_template.spec else {
+            return;
+        };
+
+        let workload_env = self.workload_env(index);
+
+        for container in pod_spec
+            .init_containers
+            .iter_mut()
+            .flatten()
+            .chain(pod_spec.containers.iter_mut())
+        {
+            let container_env = container.env.get_or_insert_with(Default::default);
+
+            for env_var in workload_env.iter() {
+                if !container_env
+                    .iter()
+                    .any(|container_env_var| container_env_var.name == env_var.name)
+                {
+                    container_env.push(env_var.clone());
+                }
+            }
+        }
+    }
+
+    /// Label selector that matches the workloads of this Benchmark.
+    pub fn workload_selector(&self) -> ListParams {
+        ListParams::default().labels(&format!(
+            "{}={}",
+            BENCHMARK_UID_LABEL,
+            self.uid().unwrap_or_default()
+        ))
+    }
+
+    /// Lists the Pods that are owned by this Benchmark, including the Pods of its Deployments, StatefulSets and Jobs.
+    pub async fn owned_pods(&self, pods_api: &Api<Pod>) -> Result<Vec<Pod>, Error> {
+        Ok(pods_api.list(&self.workload_selector()).await?.items)
+    }
+
+    /// Collects the logs of every container of the workload Pods of this Benchmark.
+    pub async fn collect_logs(&self, k8s_client: Client) -> Result<Vec<WorkloadLog>, Error> {
+        let namespace = self.workload_namespace();
+        let pods_api = Api::<Pod>::namespaced(k8s_client, &namespace);
+
+        let mut workload_logs = Vec::new();
+
+        for pod in self.owned_pods(&pods_api).await? {
+            let Some(pod_spec) = &pod.spec else {
+                continue;
+            };
+
+            let containers = pod_spec
+                .init_containers
+                .iter()
+                .flatten()
+                .chain(pod_spec.containers.iter());
+
+            for container in containers {
+                let log_params = LogParams {
+                    container: Some(container.name.clone()),
+                    timestamps: true,
+                    ..Default::default()
+                };
+
+                match pods_api.logs(&pod.name_any(), &log_params).await {
+                    Ok(logs) => workload_logs.push(WorkloadLog {
+                        benchmark_name: self.name_any(),
+                        namespace: namespace.clone(),
+                        pod: pod.name_any(),
+                        container: container.name.clone(),
+                        logs,
+                    }),
+                    // A container that never started has no logs.
+                    Err(Error::Api(err)) if err.code == 400 => {}
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        Ok(workload_logs)
+    }
+
+    /// Saves the logs of the workload Pods in the database under the run of this Benchmark.
+    /// The logs are a debugging aid, so a failure to save them only logs a warning.
+    pub async fn save_logs(&self, k8s_client: Client) {
+        let name = self.name_any();
+
+        let workload_logs = match self.collect_logs(k8s_client).await {
+            Ok(workload_logs) if workload_logs.is_empty() => return,
+            Ok(workload_logs) => workload_logs,
+            Err(err) => {
+                warn!("Could not collect the logs of Benchmark {}: {}", name, err);
+                return;
+            }
+        };
+
+        let Ok(database_url) = env::var("DATABASE_URL") else {
+            warn!("DATABASE_URL environment variable was not set, the logs of Benchmark {} are not saved.", name);
+            return;
+        };
+
+        if let Err(err) = DatabaseInterface::save_logs(
+            database_url,
+            self.uid().unwrap_or_default(),
+            workload_logs,
+        )
+        .await
+        {
+            warn!("Could not save the logs of Benchmark {}: {}", name, err);
+        }
+    }
+
+    /// Returns the first failure of the Pods and Jobs of this Benchmark, if any.
+    pub async fn workload_failure(
+        &self,
+        k8s_client: Client,
+    ) -> Result<Option<WorkloadFailure>, Error> {
+        let namespace = self.workload_namespace();
+
+        let pods = self
+            .owned_pods(&Api::<Pod>::namespaced(k8s_client.clone(), &namespace))
+            .await?;
+
+        if let Some(workload_failure) = pods.iter().find_map(pod_failure) {
+            return Ok(Some(workload_failure));
+        }
+
+        let jobs = Api::<Job>::namespaced(k8s_client, &namespace)
+            .list(&self.workload_selector())
+            .await?
+            .items;
+
+        Ok(jobs.iter().find_map(job_failure))
+    }
+
+    /// Removes the workloads of this Benchmark (i.e., the objects labelled with its UID, the objects created from its manifests
+    /// and the Helm releases it installed).
+    pub async fn remove_workloads(&self, k8s_client: Client) -> Result<(), Error> {
+        let namespace = self.namespace().unwrap_or("default".to_string());
+
+        // The latest status, which holds the Helm releases and manifests that were created after this Benchmark was read.
+        let benchmark = Api::<Benchmark>::namespaced(k8s_client.clone(), &namespace)
+            .get_status(&self.name_any())
+            .await?;
+
+        // Remove the controllers first, so they don't recreate the removed Pods.
+        // Background propagation, because Jobs orphan their Pods by default.
+        self.delete_owned::<Deployment>(k8s_client.clone()).await?;
+        self.delete_owned::<StatefulSet>(k8s_client.clone()).await?;
+        self.delete_owned::<Job>(k8s_client.clone()).await?;
+        self.delete_owned::<Service>(k8s_client.clone()).await?;
+        self.delete_owned::<Pod>(k8s_client.clone()).await?;
+
+        benchmark.remove_manifests(k8s_client.clone()).await?;
+
+        let helm_releases = benchmark.helm_releases();
+        helm::uninstall(&self.workload_namespace(), &helm_releases).await?;
+
+        self.remove_workload_namespace(k8s_client.clone()).await?;
+
+        let note = if helm_releases.is_empty() {
+            "Removed the workloads of the Benchmark.".to_string()
+        } else {
+            format!(
+                "Removed the workloads of the Benchmark, uninstalled Helm releases {}.",
+                helm_releases.join(", ")
+            )
+        };
+        self.publish_event(
+            k8s_client,
+            EventType::Normal,
+            "WorkloadsRemoved",
+            &note,
+            None,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Deletes the objects of type `K` that are labelled with the UID of this Benchmark.
+    async fn delete_owned<K>(&self, k8s_client: Client) -> Result<(), Error>
+    where
+        K: Resource<Scope = NamespaceResourceScope, DynamicType = ()>
+            + Clone
+            + Debug
+            + DeserializeOwned,
+    {
+        let namespace = self.workload_namespace();
+
+        Api::<K>::namespaced(k8s_client, &namespace)
+            .delete_collection(&DeleteParams::background(), &self.workload_selector())
+            .await
+            .map(|_| ())
+    }
+
+    /// Deletes the objects that were created from the inline manifests of this Benchmark.
+    pub async fn remove_manifests(&self, k8s_client: Client) -> Result<(), Error> {
+        let manifests = self
+            .status
+            .as_ref()
+            .map(|status| status.manifests.clone())
+            .unwrap_or_default();
+
+        for manifest in manifests.iter() {
+            let (Some(api_version), Some(kind), Some(name)) =
+                (&manifest.api_version, &manifest.kind, &manifest.name)
+            else {
+                continue;
+            };
+
+            let gvk = api_version
+                .parse::<GroupVersion>()
+                .map_err(|err| workload_error(err.to_string()))?
+                .with_kind(kind);
+            let (api_resource, _) = discovery::pinned_kind(&k8s_client, &gvk).await?;
+
+            let object_api = match &manifest.namespace {
+                Some(namespace) => Api::<DynamicObject>::namespaced_with(
+                    k8s_client.clone(),
+                    namespace,
+                    &api_resource,
+                ),
+                None => Api::<DynamicObject>::all_with(k8s_client.clone(), &api_resource),
+            };
+
+            match object_api.delete(name, &DeleteParams::background()).await {
+                // Already removed.
+                Err(Error::Api(err)) if err.code == 404 => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Names of the Helm releases the operator installed for this Benchmark.
+    pub fn helm_releases(&self) -> Vec<String> {
+        self.status
+            .as_ref()
+            .map(|status| status.helm_releases.clone())
+            .unwrap_or_default()
+    }
+
+    /// Name of the Helm release of the workload at `index`, generated names are limited to 53 characters.
+    pub fn helm_release_name(&self, helm_spec: &HelmSpec, index: usize) -> String {
+        if let Some(release_name) = &helm_spec.release_name {
+            return release_name.clone();
+        }
+
+        // Helm limits release names to 53 characters.
+        child_name(&self.name_any(), &format!("-{index}"), 53)
+    }
+}
+
+/// Name of the environment variable of the matrix parameter `key` (e.g., `MATRIX_MESSAGE_SIZE` for `message-size`).
+fn matrix_env_name(key: &str) -> String {
+    let key: String = key
+        .chars()
+        .map(|character| {
+            if character.is_ascii_alphanumeric() {
+                character.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("MATRIX_{key}")
+}
+
+/// Waiting reasons of a container from which the container does not recover by itself.
+const UNRECOVERABLE_WAITING_REASONS: [&str; 5] = [
+    "CrashLoopBackOff",
+    "ImagePullBackOff",
+    "InvalidImageName",
+    "CreateContainerConfigError",
+    "CreateContainerError",
+];
+
+/// Returns why the Pod failed, if it failed or one of its containers can't recover.
+/// Failed Pods and crashing containers of a Job are retried by the Job, these only fail the Benchmark when the Job fails.
+fn pod_failure(pod: &Pod) -> Option<WorkloadFailure> {
+    let status = pod.status.as_ref()?;
+
+    let is_job_pod = pod
+        .owner_references()
+        .iter()
+        .any(|owner_reference| owner_reference.kind == "Job");
+
+    let container_statuses: Vec<&ContainerStatus> = status
+        .init_container_statuses
+        .iter()
+        .chain(status.container_statuses.iter())
+        .flatten()
+        .collect();
+
+    let failure = |container_status: &ContainerStatus,
+                   reason: String,
+                   message: Option<String>,
+                   exit_code: Option<i32>| WorkloadFailure {
+        kind: "Pod".into(),
+        name: pod.name_any(),
+        container: Some(container_status.name.clone()),
+        reason,
+        message,
+        exit_code,
+    };
+
+    if status.phase.as_deref() == Some("Failed") {
+        if is_job_pod {
+            return None;
+        }
+
+        // Copy the termination reason of the container that failed (e.g., `OOMKilled` or `Error`).
+        let terminated_container = container_statuses.iter().find_map(|container_status| {
+            container_status
+                .state
+                .as_ref()
+                .and_then(|state| state.terminated.as_ref())
+                .filter(|terminated| terminated.exit_code != 0)
+                .map(|terminated| (container_status, terminated))
+        });
+
+        return Some(match terminated_container {
+            Some((container_status, terminated)) => failure(
+                container_status,
+                terminated.reason.clone().unwrap_or("Error".into()),
+                terminated.message.clone(),
+                Some(terminated.exit_code),
+            ),
+            // The Pod itself failed (e.g., it was evicted).
+            None => WorkloadFailure {
+                kind: "Pod".into(),
+                name: pod.name_any(),
+                reason: status.reason.clone().unwrap_or("PodFailed".into()),
+                message: status.message.clone(),
+                ..Default::default()
+            },
+        });
+    }
+
+    container_statuses.into_iter().find_map(|container_status| {
+        let state = container_status.state.as_ref()?;
+        let last_terminated = container_status
+            .last_state
+            .as_ref()
+            .and_then(|last_state| last_state.terminated.as_ref());
+
+        if let Some(terminated) = state.terminated.as_ref().or(last_terminated) {
+            if terminated.reason.as_deref() == Some("OOMKilled") && !is_job_pod {
+                return Some(failure(
+                    container_status,
+                    "OOMKilled".into(),
+                    terminated.message.clone(),
+                    Some(terminated.exit_code),
+                ));
+            }
+        }
+
+        let waiting = state.waiting.as_ref()?;
+        let reason = waiting
+            .reason
+            .as_deref()
+            .filter(|reason| UNRECOVERABLE_WAITING_REASONS.contains(reason))?;
+
+        if reason == "CrashLoopBackOff" && is_job_pod {
+            return None;
+        }
+
+        Some(failure(
+            container_status,
+            reason.into(),
+            waiting.message.clone(),
+            last_terminated.map(|terminated| terminated.exit_code),
+        ))
+    })
+}
+
+/// Returns why the Job failed, if it gave up on its Pods (e.g., `BackoffLimitExceeded`).
+fn job_failure(job: &Job) -> Option<WorkloadFailure> {
+    job.status
+        .as_ref()?
+        .conditions
+        .as_ref()?
+        .iter()
+        .find(|condition| condition.type_ == "Failed" && condition.status == "True")
+        .map(|condition| WorkloadFailure {
+            kind: "Job".into(),
+            name: job.name_any(),
+            reason: condition.reason.clone().unwrap_or("JobFailed".into()),
+            message: condition.message.clone(),
+            ..Default::default()
+        })
+}
+
+fn workload_error(reason: String) -> Error {
+    Error::Api(ErrorResponse {
+        status: "Error".into(),
+        message: "Workload could not be created.".into(),
+        reason,
+        code: 400,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmark_controller::BenchmarkSpec;
+    use k8s_openapi::{
+        api::{
+            batch::v1::{JobCondition, JobStatus},
+            core::v1::{
+                Container, ContainerState, ContainerStateTerminated, ContainerStateWaiting,
+                PodSpec, PodStatus,
+            },
+        },
+        apimachinery::pkg::apis::meta::v1::OwnerReference,
+    };
+
+    fn benchmark(workloads: Vec<BenchmarkWorkload>) -> Benchmark {
+        Benchmark::new(
+            "benchmark",
+            BenchmarkSpec {
+                workloads,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn workload(name: &str, depends_on: &[&str]) -> BenchmarkWorkload {
+        BenchmarkWorkload {
+            name: Some(name.into()),
+            depends_on: depends_on.iter().map(|name| name.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn validate_dependencies_accepts_acyclic_dependencies() {
+        let benchmark = benchmark(vec![
+            workload("client", &["server", "database"]),
+            workload("server", &["database"]),
+            workload("database", &[]),
+        ]);
+
+        assert_eq!(benchmark.validate_dependencies(), Ok(()));
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_unknown_workloads() {
+        let benchmark = benchmark(vec![workload("client", &["server"])]);
+
+        assert_eq!(
+            benchmark.validate_dependencies(),
+            Err("Workload client depends on unknown workload server.".into())
+        );
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_circular_dependencies() {
+        let benchmark = benchmark(vec![
+            workload("a", &["c"]),
+            workload("b", &["a"]),
+            workload("c", &["b"]),
+            workload("d", &[]),
+        ]);
+
+        assert_eq!(
+            benchmark.validate_dependencies(),
+            Err("The workloads have circular dependencies.".into())
+        );
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_self_dependencies() {
+        let benchmark = benchmark(vec![workload("server", &["server"])]);
+
+        assert!(benchmark.validate_dependencies().is_err());
+    }
+
+    #[test]
+    fn validate_dependencies_rejects_duplicate_names() {
+        let benchmark = benchmark(vec![workload("server", &[]), workload("server", &[])]);
+
+        assert_eq!(
+            benchmark.validate_dependencies(),
+            Err("Workload name server is used more than once.".into())
+        );
+    }
+
+    #[test]
+    fn unnamed_workloads_depend_on_generated_names() {
+        let benchmark = benchmark(vec![
+            BenchmarkWorkload::default(),
+            workload("client", &["benchmark-0"]),
+        ]);
+
+        assert_eq!(benchmark.validate_dependencies(), Ok(()));
+    }
+
+    fn pod(phase: &str, container_statuses: Vec<ContainerStatus>, is_job_pod: bool) -> Pod {
+        let mut pod = Pod {
+            status: Some(PodStatus {
+                phase: Some(phase.into()),
+                container_statuses: Some(container_statuses),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        pod.metadata.name = Some("workload".into());
+
+        if is_job_pod {
+            pod.metadata.owner_references = Some(vec![OwnerReference {
+                kind: "Job".into(),
+                ..Default::default()
+            }]);
+        }
+
+        pod
+    }
+
+    fn terminated(reason: &str, exit_code: i32) -> ContainerStatus {
+        ContainerStatus {
+            name: "main".into(),
+            state: Some(ContainerState {
+                terminated: Some(ContainerStateTerminated {
+                    reason: Some(reason.into()),
+                    exit_code,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn waiting(reason: &str) -> ContainerStatus {
+        ContainerStatus {
+            name: "main".into(),
+            state: Some(ContainerState {
+                waiting: Some(ContainerStateWaiting {
+                    reason: Some(reason.into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pod_failure_reports_the_failed_container() {
+        let failure = pod_failure(&pod("Failed", vec![terminated("Error", 2)], false)).unwrap();
+
+        assert_eq!(failure.kind, "Pod");
+        assert_eq!(failure.container.as_deref(), Some("main"));
+        assert_eq!(failure.reason, "Error");
+        assert_eq!(failure.exit_code, Some(2));
+    }
+
+    #[test]
+    fn pod_failure_reports_oom_killed_containers_of_running_pods() {
+        let failure =
+            pod_failure(&pod("Running", vec![terminated("OOMKilled", 137)], false)).unwrap();
+
+        assert_eq!(failure.reason, "OOMKilled");
+        assert_eq!(failure.exit_code, Some(137));
+    }
+
+    #[test]
+    fn pod_failure_reports_unrecoverable_waiting_reasons() {
+        let failure =
+            pod_failure(&pod("Pending", vec![waiting("ImagePullBackOff")], false)).unwrap();
+
+        assert_eq!(failure.reason, "ImagePullBackOff");
+    }
+
+    #[test]
+    fn pod_failure_ignores_recoverable_states() {
+        assert!(pod_failure(&pod("Pending", vec![waiting("ContainerCreating")], false)).is_none());
+        assert!(pod_failure(&pod("Succeeded", vec![terminated("Completed", 0)], false)).is_none());
+        assert!(pod_failure(&Pod::default()).is_none());
+    }
+
+    #[test]
+    fn pod_failure_leaves_retries_to_the_job() {
+        assert!(pod_failure(&pod("Failed", vec![terminated("Error", 1)], true)).is_none());
+        assert!(pod_failure(&pod("Running", vec![waiting("CrashLoopBackOff")], true)).is_none());
+        assert!(pod_failure(&pod("Pending", vec![waiting("ImagePullBackOff")], true)).is_some());
+    }
+
+    #[test]
+    fn pod_failure_reports_evicted_pods() {
+        let mut evicted = pod("Failed", Vec::new(), false);
+        evicted.status.as_mut().unwrap().reason = Some("Evicted".into());
+
+        let failure = pod_failure(&evicted).unwrap();
+
+        assert_eq!(failure.reason, "Evicted");
+        assert_eq!(failure.container, None);
+    }
+
+    fn job(condition_type: &str, status: &str) -> Job {
+        let mut job = Job {
+            status: Some(JobStatus {
+                conditions: Some(vec![JobCondition {
+                    type_: condition_type.into(),
+                    status: status.into(),
+                    reason: Some("BackoffLimitExceeded".into()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        job.metadata.name = Some("workload".into());
+
+        job
+    }
+
+    #[test]
+    fn job_failure_reports_failed_jobs() {
+        let failure = job_failure(&job("Failed", "True")).unwrap();
+
+        assert_eq!(failure.kind, "Job");
+        assert_eq!(failure.reason, "BackoffLimitExceeded");
+    }
+
+    #[test]
+    fn job_failure_ignores_other_conditions() {
+        assert!(job_failure(&job("Failed", "False")).is_none());
+        assert!(job_failure(&job("Complete", "True")).is_none());
+        assert!(job_failure(&Job::default()).is_none());
+    }
+
+    fn env_value(container: &Container, name: &str) -> Option<String> {
+        container
+            .env
+            .iter()
+            .flatten()
+            .find(|env_var| env_var.name == name)
+            .and_then(|env_var| env_var.value.clone())
+    }
+
+    #[test]
+    fn inject_workload_env_adds_env_to_every_container() {
+        let mut pod_template = PodTemplateSpec {
+            spec: Some(PodSpec {
+                init_containers: Some(vec![Container::default()]),
+                containers: vec![Container::default()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        benchmark(vec![]).inject_workload_env(&mut pod_template, 2);
+
+        let pod_spec = pod_template.spec.unwrap();
+
+        for container in pod_spec
+            .init_containers
+            .iter()
+            .flatten()
+            .chain(pod_spec.containers.iter())
+        {
+            assert_eq!(
+                env_value(container, "BENCHMARK_NAME").as_deref(),
+                Some("benchmark")
+            );
+            assert_eq!(
+                env_value(container, "BENCHMARK_WORKLOAD_INDEX").as_deref(),
+                Some("2")
+            );
+        }
+    }
+
+    #[test]
+    fn inject_workload_env_keeps_container_env() {
+        let mut pod_template = PodTemplateSpec {
+            spec: Some(PodSpec {
+                containers: vec![Container {
+                    env: Some(vec![EnvVar {
+                        name: "BENCHMARK_NAME".into(),
+                        value: Some("custom".into()),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        benchmark(vec![]).inject_workload_env(&mut pod_template, 0);
+
+        let container = &pod_template.spec.unwrap().containers[0];
+        let env = container.env.as_ref().unwrap();
+
+        assert_eq!(
+            env.iter()
+                .filter(|env_var| env_var.name == "BENCHMARK_NAME")
+                .count(),
+            1
+        );
+        assert_eq!(
+            env_value(container, "BENCHMARK_NAME").as_deref(),
+            Some("custom")
+        );
+        assert_eq!(
+            env_value(container, "BENCHMARK_WORKLOAD_INDEX").as_deref(),
+            Some("0")
+        );
+    }
+}
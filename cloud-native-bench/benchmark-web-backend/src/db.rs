@@ -1,14 +1,32 @@
-use crate::types::{BenchmarkListResponse, BenchmarkResult};
+use crate::types::{
+    BenchmarkEnvironment, BenchmarkListFilter, BenchmarkListResponse, BenchmarkLog,
+    BenchmarkResourceUsage, BenchmarkResult, BenchmarkSuiteResponse, BenchmarkSuiteRun,
+};
 use sqlx::{Pool, Postgres};
 use std::{error::Error, sync::Arc};
 
-/// Get full list of completed Benchmarks
+/// Get list of completed Benchmarks, filtered on the environment of their run
 pub async fn get_benchmark_list(
+    filter: BenchmarkListFilter,
     pool: Arc<Pool<Postgres>>,
 ) -> Result<Vec<BenchmarkListResponse>, Box<dyn Error>> {
-    let benchmark_results = sqlx::query_as::<_, BenchmarkResult>("SELECT * FROM benchmark_results")
+    let benchmark_results = if filter.is_empty() {
+        sqlx::query_as::<_, BenchmarkResult>("SELECT * FROM benchmark_results")
+            .fetch_all(pool.as_ref())
+            .await?
+    } else {
+        sqlx::query_as::<_, BenchmarkResult>(
+            "SELECT * FROM benchmark_results WHERE run_id IN (SELECT run_id FROM benchmark_environments WHERE ($1::text IS NULL OR node = $1) AND ($2::text IS NULL OR kubernetes_version = $2) AND ($3::text IS NULL OR container_runtime_version = $3) AND ($4::text IS NULL OR kernel_version = $4) AND ($5::text IS NULL OR architecture = $5) AND ($6::text IS NULL OR cpu_model = $6))",
+        )
+        .bind(filter.node)
+        .bind(filter.kubernetes_version)
+        .bind(filter.container_runtime_version)
+        .bind(filter.kernel_version)
+        .bind(filter.architecture)
+        .bind(filter.cpu_model)
         .fetch_all(pool.as_ref())
-        .await?;
+        .await?
+    };
 
     Ok(benchmark_results
         .iter()
@@ -34,3 +52,101 @@ pub async fn get_benchmark_detail(
 
     Ok(benchmark)
 }
+
+/// Get the logs of the workloads of a Benchmark run
+pub async fn get_benchmark_logs(
+    run_id: &str,
+    pool: Arc<Pool<Postgres>>,
+) -> Result<Vec<BenchmarkLog>, Box<dyn Error>> {
+    let benchmark_logs = sqlx::query_as::<_, BenchmarkLog>(
+        "SELECT * FROM benchmark_logs WHERE run_id = $1 ORDER BY id",
+    )
+    .bind(run_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(benchmark_logs)
+}
+
+/// Get the resource usage samples of the workloads of a Benchmark run
+pub async fn get_benchmark_resource_usage(
+    run_id: &str,
+    pool: Arc<Pool<Postgres>>,
+) -> Result<Vec<BenchmarkResourceUsage>, Box<dyn Error>> {
+    let benchmark_resource_usage = sqlx::query_as::<_, BenchmarkResourceUsage>(
+        "SELECT * FROM benchmark_resource_usage WHERE run_id = $1 ORDER BY timestamp, id",
+    )
+    .bind(run_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(benchmark_resource_usage)
+}
+
+/// Get the environment of the workloads of a Benchmark run
+pub async fn get_benchmark_environments(
+    run_id: &str,
+    pool: Arc<Pool<Postgres>>,
+) -> Result<Vec<BenchmarkEnvironment>, Box<dyn Error>> {
+    let benchmark_environments = sqlx::query_as::<_, BenchmarkEnvironment>(
+        "SELECT * FROM benchmark_environments WHERE run_id = $1 ORDER BY id",
+    )
+    .bind(run_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(benchmark_environments)
+}
+
+/// Get the suite run a Benchmark run is part of, if any
+pub async fn get_benchmark_suite_run(
+    run_id: &str,
+    pool: Arc<Pool<Postgres>>,
+) -> Result<Option<BenchmarkSuiteRun>, Box<dyn Error>> {
+    let benchmark_suite_run = sqlx::query_as::<_, BenchmarkSuiteRun>(
+        "SELECT * FROM benchmark_suite_runs WHERE run_id = $1 LIMIT 1",
+    )
+    .bind(run_id)
+    .fetch_optional(pool.as_ref())
+    .await?;
+
+    Ok(benchmark_suite_run)
+}
+
+/// Get the results of the Benchmarks of a suite run, `None` when the suite run is unknown
+pub async fn get_benchmark_suite(
+    suite_run_id: &str,
+    pool: Arc<Pool<Postgres>>,
+) -> Result<Option<BenchmarkSuiteResponse>, Box<dyn Error>> {
+    let Some(benchmark_suite_run) = sqlx::query_as::<_, BenchmarkSuiteRun>(
+        "SELECT * FROM benchmark_suite_runs WHERE suite_run_id = $1 ORDER BY id LIMIT 1",
+    )
+    .bind(suite_run_id)
+    .fetch_optional(pool.as_ref())
+    .await?
+    else {
+        return Ok(None);
+    };
+
+    let benchmark_results = sqlx::query_as::<_, BenchmarkResult>(
+        "SELECT benchmark_results.* FROM benchmark_results JOIN benchmark_suite_runs ON benchmark_results.run_id = benchmark_suite_runs.run_id WHERE benchmark_suite_runs.suite_run_id = $1 ORDER BY benchmark_suite_runs.id, benchmark_results.id",
+    )
+    .bind(suite_run_id)
+    .fetch_all(pool.as_ref())
+    .await?;
+
+    Ok(Some(BenchmarkSuiteResponse {
+        suite_run_id: benchmark_suite_run.suite_run_id,
+        suite_name: benchmark_suite_run.suite_name,
+        namespace: benchmark_suite_run.namespace,
+        benchmarks: benchmark_results
+            .iter()
+            .map(|benchmark_result| BenchmarkListResponse {
+                id: benchmark_result.id,
+                timestamp: benchmark_result.timestamp,
+                name: benchmark_result.name.clone(),
+                description: benchmark_result.description.clone(),
+            })
+            .collect(),
+    }))
+}
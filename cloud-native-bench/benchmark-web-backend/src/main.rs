@@ -1,6 +1,7 @@
 use crate::routes::{
-    api_list_benchmark_results, download_jupyter, download_raw_data, index,
-    render_benchmark_details, render_list_benchmark_results,
+    api_benchmark_suite, api_list_benchmark_results, download_jupyter, download_logs,
+    download_raw_data, download_resource_usage, index, render_benchmark_details,
+    render_benchmark_suite, render_list_benchmark_results,
 };
 use axum::routing::get;
 use axum::{Router, Server};
@@ -43,9 +44,25 @@ async fn main() {
             "/api/benchmark-results/:id/download/jupyter",
             get(download_jupyter),
         )
+        .route(
+            "/api/benchmark-results/:id/download/logs",
+            get(download_logs),
+        )
+        .route(
+            "/api/benchmark-results/:id/download/resource-usage",
+            get(download_resource_usage),
+        )
+        .route(
+            "/api/benchmark-suites/:suite_run_id",
+            get(api_benchmark_suite),
+        )
         // HTML render
         .route("/benchmark-results", get(render_list_benchmark_results))
         .route("/benchmark-results/:id", get(render_benchmark_details))
+        .route(
+            "/benchmark-suites/:suite_run_id",
+            get(render_benchmark_suite),
+        )
         .with_state(shared_pool);
 
     // Start the server.
@@ -1,12 +1,20 @@
 use crate::{
     db,
     types::{
-        BenchmarkDetailsTemplate, BenchmarkListResponse, BenchmarkListTemplate, IndexResponse,
+        BenchmarkDetailsTemplate, BenchmarkListFilter, BenchmarkListResponse,
+        BenchmarkListTemplate, BenchmarkSuiteResponse, BenchmarkSuiteTemplate, IndexResponse,
     },
 };
 use askama::Template;
-use axum::{extract::Path, http::header, Json};
-use axum::{extract::State, response::IntoResponse};
+use axum::{
+    extract::State,
+    response::{IntoResponse, Response},
+};
+use axum::{
+    extract::{Path, Query},
+    http::header,
+    Json,
+};
 use axum::{http::StatusCode, response::Html};
 use sqlx::{Pool, Postgres};
 use std::sync::Arc;
@@ -20,11 +28,12 @@ pub async fn index() -> Json<IndexResponse> {
     })
 }
 
-/// Returns a list of all the benchmark results
+/// Returns a list of the benchmark results, optionally filtered on the environment of their run
 pub async fn api_list_benchmark_results(
+    Query(filter): Query<BenchmarkListFilter>,
     State(pool): State<Arc<Pool<Postgres>>>,
 ) -> Result<Json<Vec<BenchmarkListResponse>>, StatusCode> {
-    let benchmark_results = db::get_benchmark_list(pool.clone()).await;
+    let benchmark_results = db::get_benchmark_list(filter, pool.clone()).await;
 
     if let Ok(benchmark_results) = benchmark_results {
         Ok(Json(benchmark_results))
@@ -33,15 +42,17 @@ pub async fn api_list_benchmark_results(
     }
 }
 
-/// Render an HTML page with a list of all the completed Benchmarks
+/// Render an HTML page with a list of the completed Benchmarks, optionally filtered on the environment of their run
 pub async fn render_list_benchmark_results(
+    Query(filter): Query<BenchmarkListFilter>,
     State(pool): State<Arc<Pool<Postgres>>>,
 ) -> impl IntoResponse {
-    let benchmark_results = db::get_benchmark_list(pool.clone()).await;
+    let benchmark_results = db::get_benchmark_list(filter.clone(), pool.clone()).await;
 
     if let Ok(benchmark_results) = benchmark_results {
         let template = BenchmarkListTemplate {
             benchmarks: benchmark_results,
+            filter,
         };
 
         match template.render() {
@@ -66,11 +77,31 @@ pub async fn render_benchmark_details(
     Path(id): Path<u32>,
     State(pool): State<Arc<Pool<Postgres>>>,
 ) -> impl IntoResponse {
-    let benchmark_details = db::get_benchmark_detail(id, pool).await;
+    let Ok(benchmark_details) = db::get_benchmark_detail(id, pool.clone()).await else {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to retrieve data from DB.",
+        )
+            .into_response();
+    };
 
-    if let Ok(benchmark_details) = benchmark_details {
+    // Results without a run (see `benchmark_run_id`) have no environments and are not part of a suite run.
+    // The errors are dropped right away, they can't be held across an await.
+    let (environments, suite_run) = match &benchmark_details.run_id {
+        Some(run_id) => (
+            db::get_benchmark_environments(run_id, pool.clone())
+                .await
+                .ok(),
+            db::get_benchmark_suite_run(run_id, pool).await.ok(),
+        ),
+        None => (Some(Vec::new()), Some(None)),
+    };
+
+    if let (Some(environments), Some(suite_run)) = (environments, suite_run) {
         let template = BenchmarkDetailsTemplate {
             benchmark: benchmark_details,
+            environments,
+            suite_run,
         };
 
         match template.render() {
@@ -90,6 +121,47 @@ pub async fn render_benchmark_details(
     }
 }
 
+/// Returns the results of the Benchmarks of a suite run
+pub async fn api_benchmark_suite(
+    Path(suite_run_id): Path<String>,
+    State(pool): State<Arc<Pool<Postgres>>>,
+) -> Result<Json<BenchmarkSuiteResponse>, StatusCode> {
+    match db::get_benchmark_suite(&suite_run_id, pool).await {
+        Ok(Some(benchmark_suite)) => Ok(Json(benchmark_suite)),
+        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Render an HTML page with the results of the Benchmarks of a suite run
+pub async fn render_benchmark_suite(
+    Path(suite_run_id): Path<String>,
+    State(pool): State<Arc<Pool<Postgres>>>,
+) -> impl IntoResponse {
+    match db::get_benchmark_suite(&suite_run_id, pool).await {
+        Ok(Some(benchmark_suite)) => {
+            let template = BenchmarkSuiteTemplate {
+                suite: benchmark_suite,
+            };
+
+            match template.render() {
+                Ok(html) => Html(html).into_response(),
+                Err(err) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to render Benchmark suite template: {}.", err),
+                )
+                    .into_response(),
+            }
+        }
+        Ok(None) => (StatusCode::NOT_FOUND).into_response(),
+        Err(_) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to retrieve data from DB.",
+        )
+            .into_response(),
+    }
+}
+
 /// Download the raw data as a txt file
 pub async fn download_raw_data(
     Path(id): Path<u32>,
@@ -145,3 +217,113 @@ pub async fn download_jupyter(
             .into_response()
     }
 }
+
+/// Download the logs of the workloads of the Benchmark run that produced the results
+pub async fn download_logs(
+    Path(id): Path<u32>,
+    State(pool): State<Arc<Pool<Postgres>>>,
+) -> impl IntoResponse {
+    let run_id = match benchmark_run_id(id, pool.clone()).await {
+        Ok(run_id) => run_id,
+        Err(response) => return response,
+    };
+
+    if let Ok(benchmark_logs) = db::get_benchmark_logs(&run_id, pool).await {
+        let logs: String = benchmark_logs
+            .iter()
+            .map(|benchmark_log| {
+                format!(
+                    "==> {}/{} <==\n{}\n",
+                    benchmark_log.pod, benchmark_log.container, benchmark_log.logs
+                )
+            })
+            .collect();
+
+        let headers = [
+            (header::CONTENT_TYPE, "text/txt; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"benchmark_{}_logs.txt\"", id),
+            ),
+        ];
+
+        (headers, logs).into_response()
+    } else {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to retrieve data from DB.",
+        )
+            .into_response()
+    }
+}
+
+/// Download the resource usage samples of the workloads of the Benchmark run that produced the results as CSV
+pub async fn download_resource_usage(
+    Path(id): Path<u32>,
+    State(pool): State<Arc<Pool<Postgres>>>,
+) -> impl IntoResponse {
+    let run_id = match benchmark_run_id(id, pool.clone()).await {
+        Ok(run_id) => run_id,
+        Err(response) => return response,
+    };
+
+    if let Ok(benchmark_resource_usage) = db::get_benchmark_resource_usage(&run_id, pool).await {
+        let header_row = "timestamp,namespace,pod,container,cpu_usage_nano_cores,memory_working_set_bytes,network_rx_bytes,network_tx_bytes,disk_used_bytes\n";
+
+        let rows: String = benchmark_resource_usage
+            .iter()
+            .map(|sample| {
+                let to_field =
+                    |value: Option<i64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+                format!(
+                    "{},{},{},{},{},{},{},{},{}\n",
+                    sample.timestamp.to_rfc3339(),
+                    sample.namespace,
+                    sample.pod,
+                    sample.container.clone().unwrap_or_default(),
+                    to_field(sample.cpu_usage_nano_cores),
+                    to_field(sample.memory_working_set_bytes),
+                    to_field(sample.network_rx_bytes),
+                    to_field(sample.network_tx_bytes),
+                    to_field(sample.disk_used_bytes)
+                )
+            })
+            .collect();
+
+        let headers = [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"benchmark_{}_resource_usage.csv\"",
+                    id
+                ),
+            ),
+        ];
+
+        (headers, format!("{header_row}{rows}")).into_response()
+    } else {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to retrieve data from DB.",
+        )
+            .into_response()
+    }
+}
+
+/// Run of the Benchmark that produced the results with `id`, responds with not found when the results have no run.
+/// Results of Benchmarks that were not started by the operator are not linked to a run.
+async fn benchmark_run_id(id: u32, pool: Arc<Pool<Postgres>>) -> Result<String, Response> {
+    let Ok(benchmark_details) = db::get_benchmark_detail(id, pool).await else {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Failed to retrieve data from DB.",
+        )
+            .into_response());
+    };
+
+    benchmark_details
+        .run_id
+        .ok_or_else(|| (StatusCode::NOT_FOUND).into_response())
+}
@@ -1,6 +1,6 @@
 use askama::Template;
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 
 /// SQLx BenchmarkResult.
@@ -12,6 +12,72 @@ pub struct BenchmarkResult {
     pub raw_data: String,
     pub timestamp: DateTime<Utc>,
     pub generated_jupyter: Option<String>,
+    pub run_id: Option<String>,
+}
+
+/// SQLx BenchmarkLog, the logs of a workload container of a Benchmark run.
+#[derive(FromRow, Debug, Clone, Serialize)]
+pub struct BenchmarkLog {
+    pub id: i32,
+    pub run_id: String,
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: String,
+    pub logs: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// SQLx BenchmarkResourceUsage, a resource usage sample of a workload Pod (or container) of a Benchmark run.
+#[derive(FromRow, Debug, Clone, Serialize)]
+pub struct BenchmarkResourceUsage {
+    pub id: i32,
+    pub run_id: String,
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub container: Option<String>,
+    pub cpu_usage_nano_cores: Option<i64>,
+    pub memory_working_set_bytes: Option<i64>,
+    pub network_rx_bytes: Option<i64>,
+    pub network_tx_bytes: Option<i64>,
+    pub disk_used_bytes: Option<i64>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// SQLx BenchmarkEnvironment, the environment a workload Pod of a Benchmark run ran in.
+#[derive(FromRow, Debug, Clone, Serialize)]
+pub struct BenchmarkEnvironment {
+    pub id: i32,
+    pub run_id: String,
+    pub benchmark_name: String,
+    pub namespace: String,
+    pub pod: String,
+    pub node: String,
+    pub kubernetes_version: String,
+    pub kubelet_version: String,
+    pub container_runtime_version: String,
+    pub kernel_version: String,
+    pub os_image: String,
+    pub architecture: String,
+    pub cpu_model: Option<String>,
+    pub allocatable_cpu: Option<String>,
+    pub allocatable_memory: Option<String>,
+    pub node_labels: String,   // JSON object.
+    pub image_digests: String, // JSON object.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// SQLx BenchmarkSuiteRun, links a Benchmark run to the run of its suite.
+#[derive(FromRow, Debug, Clone, Serialize)]
+pub struct BenchmarkSuiteRun {
+    pub id: i32,
+    pub suite_run_id: String,
+    pub suite_name: String,
+    pub namespace: String,
+    pub benchmark_name: String,
+    pub run_id: String,
+    pub timestamp: DateTime<Utc>,
 }
 
 // Askama rendering templates.
@@ -21,6 +87,7 @@ pub struct BenchmarkResult {
 #[template(path = "benchmark_list.html")]
 pub struct BenchmarkListTemplate {
     pub benchmarks: Vec<BenchmarkListResponse>,
+    pub filter: BenchmarkListFilter,
 }
 
 /// Template type for displaying details of a Benchmark.
@@ -28,6 +95,53 @@ pub struct BenchmarkListTemplate {
 #[template(path = "benchmark_details.html")]
 pub struct BenchmarkDetailsTemplate {
     pub benchmark: BenchmarkResult,
+    pub environments: Vec<BenchmarkEnvironment>,
+    pub suite_run: Option<BenchmarkSuiteRun>,
+}
+
+/// Template type for displaying the results of a suite run together.
+#[derive(Template)]
+#[template(path = "benchmark_suite.html")]
+pub struct BenchmarkSuiteTemplate {
+    pub suite: BenchmarkSuiteResponse,
+}
+
+// HTTP request types.
+
+/// Query of a Benchmark list request, only the results of runs with a workload in a matching environment are listed.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct BenchmarkListFilter {
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub node: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub kubernetes_version: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub container_runtime_version: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub kernel_version: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub architecture: Option<String>,
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub cpu_model: Option<String>,
+}
+
+impl BenchmarkListFilter {
+    /// Whether the filter lists all the results.
+    pub fn is_empty(&self) -> bool {
+        self.node.is_none()
+            && self.kubernetes_version.is_none()
+            && self.container_runtime_version.is_none()
+            && self.kernel_version.is_none()
+            && self.architecture.is_none()
+            && self.cpu_model.is_none()
+    }
+}
+
+/// Fields of the filter form that are left empty don't filter.
+fn empty_as_none<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let value = Option::<String>::deserialize(deserializer)?;
+
+    Ok(value.filter(|value| !value.is_empty()))
 }
 
 // HTTP response types.
@@ -48,3 +162,12 @@ pub struct BenchmarkListResponse {
     pub name: String,
     pub description: String,
 }
+
+/// Results of the Benchmarks of a suite run, in the order the Benchmarks started.
+#[derive(Serialize)]
+pub struct BenchmarkSuiteResponse {
+    pub suite_run_id: String,
+    pub suite_name: String,
+    pub namespace: String,
+    pub benchmarks: Vec<BenchmarkListResponse>,
+}
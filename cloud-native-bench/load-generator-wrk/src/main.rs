@@ -39,9 +39,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let database_url = grpc_call.benchmark_started().await?;
 
     // Run the benchmark and capture the results.
-    let result_response = wrk_load_test();
+    let mut result_response = wrk_load_test();
 
-    // Store the results in the database.
+    // Store the results in the database, linked to the run of the Benchmark.
+    result_response.run_id = grpc_call.run_id.clone();
     DatabaseInterface::save_results(database_url, result_response).await?;
 
     // Call `benchmark_done` to mark this benchmark as done.
@@ -136,10 +137,13 @@ fn wrk_load_test() -> ResultResponse {
     }
 
     ResultResponse {
-        name: env::var("TEST_NAME").unwrap_or("wrk load test.".to_string()),
+        name: env::var("TEST_NAME")
+            .or_else(|_| env::var("BENCHMARK_NAME"))
+            .unwrap_or("wrk load test.".to_string()),
         description: env::var("TEST_DESC").unwrap_or("wrk load test.".to_string()),
         data: vec![req_sec_de, transfer_sec_de],
         raw_data,
         generated_jupyter: None,
+        run_id: None,
     }
 }